@@ -6,7 +6,9 @@ pub mod registry_examples;
 pub mod registry_frameworks;
 pub mod registry_hooks;
 pub mod registry_lib;
+pub mod registry_resolver;
 pub mod registry_styles;
+pub mod registry_theme_generator;
 pub mod registry_themes;
 pub mod registry_ui;
 pub mod schema;
@@ -1,4 +1,9 @@
-use std::{collections::HashMap, sync::LazyLock};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    str::FromStr,
+    sync::LazyLock,
+};
 
 use crate::schema::Mode;
 
@@ -21,6 +26,272 @@ pub struct ColorScaleValue {
     pub hsl: String,
 }
 
+impl ColorValue {
+    pub fn oklch(&self) -> Oklch {
+        Rgb::from_hex(&self.hex)
+            .expect("Color should have a valid hex value.")
+            .into()
+    }
+}
+
+impl ColorScaleValue {
+    pub fn oklch(&self) -> Oklch {
+        Rgb::from_hex(&self.hex)
+            .expect("Color should have a valid hex value.")
+            .into()
+    }
+}
+
+/// A color in the sRGB color space, with channels from 0 to 1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rgb {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+}
+
+impl Rgb {
+    /// Parse a hex color, e.g. `#f8fafc` or `#fff`.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.strip_prefix('#')?;
+        let channels = match hex.len() {
+            3 => hex
+                .chars()
+                .map(|char| u8::from_str_radix(&char.to_string().repeat(2), 16).ok())
+                .collect::<Option<Vec<_>>>()?,
+            6 => (0..6)
+                .step_by(2)
+                .map(|index| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok())
+                .collect::<Option<Vec<_>>>()?,
+            _ => return None,
+        };
+
+        Some(Rgb {
+            r: f64::from(channels[0]) / 255.0,
+            g: f64::from(channels[1]) / 255.0,
+            b: f64::from(channels[2]) / 255.0,
+        })
+    }
+
+    /// Format as a hex color, clamping channels outside the sRGB gamut.
+    pub fn to_hex(self) -> String {
+        let channel = |value: f64| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+        format!(
+            "#{:02x}{:02x}{:02x}",
+            channel(self.r),
+            channel(self.g),
+            channel(self.b)
+        )
+    }
+
+    /// Convert from HSL, with hue in degrees and saturation and lightness from 0 to 1.
+    pub fn from_hsl(hue: f64, saturation: f64, lightness: f64) -> Self {
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let channel = |n: f64| {
+            let k = (n + hue / 30.0).rem_euclid(12.0);
+            lightness - chroma / 2.0 * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+        };
+
+        Rgb {
+            r: channel(0.0),
+            g: channel(8.0),
+            b: channel(4.0),
+        }
+    }
+
+    /// Format as HSL channels as used in CSS variables, e.g. `240 5.9% 10%`, clamping channels outside the sRGB gamut.
+    pub fn to_hsl_channel(self) -> String {
+        let (r, g, b) = (
+            self.r.clamp(0.0, 1.0),
+            self.g.clamp(0.0, 1.0),
+            self.b.clamp(0.0, 1.0),
+        );
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let chroma = max - min;
+        let lightness = (max + min) / 2.0;
+
+        let (hue, saturation) = if chroma == 0.0 {
+            (0.0, 0.0)
+        } else {
+            let hue = if max == r {
+                ((g - b) / chroma).rem_euclid(6.0)
+            } else if max == g {
+                (b - r) / chroma + 2.0
+            } else {
+                (r - g) / chroma + 4.0
+            };
+
+            (hue * 60.0, chroma / (1.0 - (2.0 * lightness - 1.0).abs()))
+        };
+
+        format!(
+            "{} {}% {}%",
+            format_number(hue, 1),
+            format_number(saturation * 100.0, 1),
+            format_number(lightness * 100.0, 1)
+        )
+    }
+
+    /// Relative luminance as defined by WCAG.
+    pub fn luminance(self) -> f64 {
+        0.2126 * srgb_to_linear(self.r.clamp(0.0, 1.0))
+            + 0.7152 * srgb_to_linear(self.g.clamp(0.0, 1.0))
+            + 0.0722 * srgb_to_linear(self.b.clamp(0.0, 1.0))
+    }
+
+    /// Contrast ratio as defined by WCAG, from 1 to 21.
+    pub fn contrast(self, other: Rgb) -> f64 {
+        let (lighter, darker) = if self.luminance() > other.luminance() {
+            (self.luminance(), other.luminance())
+        } else {
+            (other.luminance(), self.luminance())
+        };
+
+        (lighter + 0.05) / (darker + 0.05)
+    }
+
+    /// Parse HSL channels as used in CSS variables, e.g. `240 5.9% 10%`.
+    pub fn from_hsl_channel(value: &str) -> Option<Self> {
+        let mut parts = value.split_whitespace();
+        let hue = parts.next()?.parse::<f64>().ok()?;
+        let saturation = parts.next()?.strip_suffix('%')?.parse::<f64>().ok()?;
+        let lightness = parts.next()?.strip_suffix('%')?.parse::<f64>().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(Rgb::from_hsl(hue, saturation / 100.0, lightness / 100.0))
+    }
+}
+
+/// A color in the OKLCH color space, with lightness from 0 to 1 and hue in degrees.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Oklch {
+    pub l: f64,
+    pub c: f64,
+    pub h: f64,
+}
+
+fn srgb_to_linear(value: f64) -> f64 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f64) -> f64 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+impl From<Rgb> for Oklch {
+    fn from(rgb: Rgb) -> Self {
+        let (r, g, b) = (
+            srgb_to_linear(rgb.r),
+            srgb_to_linear(rgb.g),
+            srgb_to_linear(rgb.b),
+        );
+
+        // Linear sRGB to LMS cone responses, see https://bottosson.github.io/posts/oklab/.
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+
+        let lightness = 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s;
+        let a = 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s;
+        let b = 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s;
+
+        Oklch {
+            l: lightness,
+            c: a.hypot(b),
+            h: b.atan2(a).to_degrees().rem_euclid(360.0),
+        }
+    }
+}
+
+impl From<Oklch> for Rgb {
+    fn from(oklch: Oklch) -> Self {
+        let (a, b) = (
+            oklch.c * oklch.h.to_radians().cos(),
+            oklch.c * oklch.h.to_radians().sin(),
+        );
+
+        let l = (oklch.l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+        let m = (oklch.l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+        let s = (oklch.l - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+
+        Rgb {
+            r: linear_to_srgb(4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s),
+            g: linear_to_srgb(-1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s),
+            b: linear_to_srgb(-0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s),
+        }
+    }
+}
+
+/// Format a number with at most `decimals` decimals, without trailing zeros.
+fn format_number(value: f64, decimals: usize) -> String {
+    let value = format!("{value:.decimals$}");
+    let value = value.trim_end_matches('0').trim_end_matches('.');
+
+    match value {
+        "-0" => "0".into(),
+        value => value.into(),
+    }
+}
+
+impl Display for Oklch {
+    /// Format as a CSS color, e.g. `oklch(0.6231 0.188 259.815)`, precise enough to round-trip 8-bit sRGB.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let chroma = format_number(self.c, 4);
+        // Hue is meaningless for achromatic colors.
+        let hue = if chroma == "0" {
+            "0".into()
+        } else {
+            format_number(self.h, 3)
+        };
+
+        write!(f, "oklch({} {chroma} {hue})", format_number(self.l, 4))
+    }
+}
+
+impl FromStr for Oklch {
+    type Err = String;
+
+    /// Parse a CSS color, e.g. `oklch(0.623 0.214 259.815)` or `oklch(62.3% 0.214 259.815)`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid OKLCH color `{value}`.");
+
+        let channels = value
+            .trim()
+            .strip_prefix("oklch(")
+            .and_then(|value| value.strip_suffix(')'))
+            .ok_or_else(invalid)?
+            .split_whitespace()
+            .collect::<Vec<_>>();
+        let [lightness, chroma, hue] = channels[..] else {
+            return Err(invalid());
+        };
+
+        let lightness = match lightness.strip_suffix('%') {
+            Some(lightness) => lightness.parse::<f64>().map(|lightness| lightness / 100.0),
+            None => lightness.parse::<f64>(),
+        }
+        .map_err(|_| invalid())?;
+
+        Ok(Oklch {
+            l: lightness,
+            c: chroma.parse().map_err(|_| invalid())?,
+            h: hue.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
 pub static COLORS: LazyLock<HashMap<String, Color>> = LazyLock::new(|| {
     HashMap::from([
         ("inherit".into(), Color::String("inherit".into())),
@@ -1669,3 +1940,127 @@ pub static COLOR_MAPPING: LazyLock<HashMap<Mode, HashMap<String, String>>> = Laz
         ),
     ])
 });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex_values() -> Vec<&'static str> {
+        COLORS
+            .values()
+            .flat_map(|color| match color {
+                Color::String(_) => vec![],
+                Color::Value(value) => vec![value.hex.as_str()],
+                Color::Values(values) => values.iter().map(|value| value.hex.as_str()).collect(),
+            })
+            .collect()
+    }
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn converts_reference_colors() {
+        // Reference values from https://bottosson.github.io/posts/oklab/ and CSS Color 4.
+        let red = Oklch::from(Rgb::from_hex("#ff0000").unwrap());
+        assert_close(red.l, 0.62796, 1e-4);
+        assert_close(red.c, 0.25768, 1e-4);
+        assert_close(red.h, 29.2339, 1e-3);
+
+        let blue = Oklch::from(Rgb::from_hex("#0000ff").unwrap());
+        assert_close(blue.l, 0.45201, 1e-4);
+        assert_close(blue.c, 0.31321, 1e-4);
+        assert_close(blue.h, 264.052, 1e-3);
+    }
+
+    #[test]
+    fn formats_achromatic_colors() {
+        assert_eq!(
+            Oklch::from(Rgb::from_hex("#ffffff").unwrap()).to_string(),
+            "oklch(1 0 0)"
+        );
+        assert_eq!(
+            Oklch::from(Rgb::from_hex("#000").unwrap()).to_string(),
+            "oklch(0 0 0)"
+        );
+    }
+
+    #[test]
+    fn round_trips_palette() {
+        for hex in hex_values() {
+            let rgb = Rgb::from_hex(hex).unwrap();
+            let round_trip = Rgb::from(Oklch::from(rgb));
+
+            assert_close(round_trip.r, rgb.r, 1e-6);
+            assert_close(round_trip.g, rgb.g, 1e-6);
+            assert_close(round_trip.b, rgb.b, 1e-6);
+            assert_eq!(round_trip.to_hex(), hex);
+        }
+    }
+
+    #[test]
+    fn round_trips_palette_through_css() {
+        for hex in hex_values() {
+            let css = Oklch::from(Rgb::from_hex(hex).unwrap()).to_string();
+            let oklch = css.parse::<Oklch>().unwrap();
+
+            assert_eq!(Rgb::from(oklch).to_hex(), hex, "{css}");
+        }
+    }
+
+    #[test]
+    fn parses_css() {
+        let oklch = "oklch(62.3% 0.214 259.815)".parse::<Oklch>().unwrap();
+        assert_eq!(
+            oklch,
+            Oklch {
+                l: 0.623,
+                c: 0.214,
+                h: 259.815
+            }
+        );
+
+        assert!("oklch(0.5 0.1)".parse::<Oklch>().is_err());
+        assert!("rgb(0 0 0)".parse::<Oklch>().is_err());
+    }
+
+    #[test]
+    fn converts_hsl_channels() {
+        assert_eq!(
+            Rgb::from_hsl_channel("0 0% 100%").unwrap().to_hex(),
+            "#ffffff"
+        );
+        // Tailwind CSS `slate-900`.
+        assert_eq!(
+            Rgb::from_hsl_channel("222.2 47.4% 11.2%").unwrap().to_hex(),
+            "#0f172a"
+        );
+        assert!(Rgb::from_hsl_channel("0 0 100").is_none());
+
+        for channel in [
+            "0 0% 100%",
+            "240 5.9% 10%",
+            "222.2 47.4% 11.2%",
+            "0 84.2% 60.2%",
+        ] {
+            assert_eq!(
+                Rgb::from_hsl_channel(channel).unwrap().to_hsl_channel(),
+                channel
+            );
+        }
+    }
+
+    #[test]
+    fn computes_contrast() {
+        let white = Rgb::from_hex("#ffffff").unwrap();
+        let black = Rgb::from_hex("#000000").unwrap();
+
+        assert_close(white.contrast(black), 21.0, 1e-9);
+        assert_close(black.contrast(white), 21.0, 1e-9);
+        assert_close(white.contrast(white), 1.0, 1e-9);
+    }
+}
@@ -0,0 +1,238 @@
+use std::{
+    collections::HashSet,
+    error::Error,
+    fmt::{self, Display},
+};
+
+use crate::{
+    REGISTRY,
+    schema::{FrameworkName, RegistryEntry, Style},
+};
+
+/// Registry items in the order they should be installed, with dependencies before their dependents.
+#[derive(Clone, Debug)]
+pub struct InstallPlan {
+    pub framework: FrameworkName,
+    pub style: Style,
+    pub items: Vec<RegistryEntry>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ResolveError {
+    /// An item could not be found. The path leads from a requested item to the missing item.
+    MissingItem { name: String, path: Vec<String> },
+    /// Items depend on each other. The path starts and ends with the same item.
+    Cycle { path: Vec<String> },
+}
+
+impl Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResolveError::MissingItem { name, path } if path.len() > 1 => write!(
+                f,
+                "Registry item `{name}` not found, required by {}.",
+                path[..path.len() - 1].join(" -> ")
+            ),
+            ResolveError::MissingItem { name, .. } => {
+                write!(f, "Registry item `{name}` not found.")
+            }
+            ResolveError::Cycle { path } => {
+                write!(f, "Registry dependency cycle: {}.", path.join(" -> "))
+            }
+        }
+    }
+}
+
+impl Error for ResolveError {}
+
+/// Resolve items and their registry dependencies from the compiled-in [`REGISTRY`].
+pub fn resolve_registry_items(
+    names: &[String],
+    framework: FrameworkName,
+    style: Style,
+) -> Result<InstallPlan, ResolveError> {
+    let registry = REGISTRY.get(&framework);
+
+    resolve_registry_items_with(names, framework, style, |name| {
+        registry
+            .into_iter()
+            .flatten()
+            .find(|entry| entry.name == name)
+            .cloned()
+    })
+}
+
+/// Resolve items and their registry dependencies, looking up each item with `lookup`.
+///
+/// Shared dependencies are included once. Dependencies are ordered before the items that require them.
+pub fn resolve_registry_items_with<F>(
+    names: &[String],
+    framework: FrameworkName,
+    style: Style,
+    mut lookup: F,
+) -> Result<InstallPlan, ResolveError>
+where
+    F: FnMut(&str) -> Option<RegistryEntry>,
+{
+    let mut resolver = Resolver {
+        lookup: &mut lookup,
+        items: vec![],
+        resolved: HashSet::new(),
+        path: vec![],
+    };
+
+    for name in names {
+        resolver.visit(name)?;
+    }
+
+    Ok(InstallPlan {
+        framework,
+        style,
+        items: resolver.items,
+    })
+}
+
+struct Resolver<'a, F> {
+    lookup: &'a mut F,
+    items: Vec<RegistryEntry>,
+    resolved: HashSet<String>,
+    path: Vec<String>,
+}
+
+impl<F> Resolver<'_, F>
+where
+    F: FnMut(&str) -> Option<RegistryEntry>,
+{
+    fn visit(&mut self, name: &str) -> Result<(), ResolveError> {
+        if self.resolved.contains(name) {
+            return Ok(());
+        }
+
+        self.path.push(name.into());
+
+        if let Some(index) = self.path[..self.path.len() - 1]
+            .iter()
+            .position(|item| item == name)
+        {
+            return Err(ResolveError::Cycle {
+                path: self.path[index..].to_vec(),
+            });
+        }
+
+        let Some(entry) = (self.lookup)(name) else {
+            return Err(ResolveError::MissingItem {
+                name: name.into(),
+                path: self.path.clone(),
+            });
+        };
+
+        for dependency in entry.registry_dependencies.iter().flatten() {
+            self.visit(dependency)?;
+        }
+
+        self.path.pop();
+        self.resolved.insert(name.into());
+        self.items.push(entry);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::RegistryItemType;
+
+    fn entry(name: &str, registry_dependencies: &[&str]) -> RegistryEntry {
+        RegistryEntry {
+            name: name.into(),
+            r#type: RegistryItemType::Ui,
+            description: None,
+            dependencies: None,
+            dev_dependencies: None,
+            registry_dependencies: Some(
+                registry_dependencies
+                    .iter()
+                    .map(|dependency| dependency.to_string())
+                    .collect(),
+            ),
+            files: None,
+            tailwind: None,
+            css_vars: None,
+            source: None,
+            category: None,
+            subcategory: None,
+            chunks: None,
+            docs: None,
+        }
+    }
+
+    fn resolve(names: &[&str], entries: &[RegistryEntry]) -> Result<Vec<String>, ResolveError> {
+        let names = names
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>();
+
+        resolve_registry_items_with(&names, FrameworkName::Leptos, Style::Default, |name| {
+            entries.iter().find(|entry| entry.name == name).cloned()
+        })
+        .map(|plan| plan.items.into_iter().map(|item| item.name).collect())
+    }
+
+    #[test]
+    fn orders_dependencies_first() {
+        let entries = [
+            entry("utils", &[]),
+            entry("button", &["utils"]),
+            entry("dialog", &["button", "utils"]),
+            entry("alert-dialog", &["dialog", "button"]),
+        ];
+
+        assert_eq!(
+            resolve(&["alert-dialog", "button"], &entries).unwrap(),
+            ["utils", "button", "dialog", "alert-dialog"]
+        );
+    }
+
+    #[test]
+    fn reports_missing_item_with_path() {
+        let entries = [entry("dialog", &["button"]), entry("button", &["utils"])];
+
+        let error = resolve(&["dialog"], &entries).unwrap_err();
+
+        assert_eq!(
+            error,
+            ResolveError::MissingItem {
+                name: "utils".into(),
+                path: vec!["dialog".into(), "button".into(), "utils".into()],
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Registry item `utils` not found, required by dialog -> button."
+        );
+        assert_eq!(
+            resolve(&["card"], &entries).unwrap_err().to_string(),
+            "Registry item `card` not found."
+        );
+    }
+
+    #[test]
+    fn reports_cycle() {
+        let entries = [entry("a", &["b"]), entry("b", &["c"]), entry("c", &["b"])];
+
+        let error = resolve(&["a"], &entries).unwrap_err();
+
+        assert_eq!(
+            error,
+            ResolveError::Cycle {
+                path: vec!["b".into(), "c".into(), "b".into()],
+            }
+        );
+        assert_eq!(error.to_string(), "Registry dependency cycle: b -> c -> b.");
+        assert!(matches!(
+            resolve(&["a"], &[entry("a", &["a"])]),
+            Err(ResolveError::Cycle { .. })
+        ));
+    }
+}
@@ -0,0 +1,270 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{self, Display},
+};
+
+use crate::{
+    registry_base_colors::BASE_COLORS,
+    registry_colors::{Oklch, Rgb},
+    schema::{Mode, RegistryItemCssVars},
+};
+
+/// Minimum contrast ratio for normal text according to WCAG AA.
+pub const WCAG_AA_CONTRAST: f64 = 4.5;
+
+/// Background and foreground variables which are used together.
+pub const CONTRAST_PAIRS: [(&str, &str); 8] = [
+    ("background", "foreground"),
+    ("card", "card-foreground"),
+    ("popover", "popover-foreground"),
+    ("primary", "primary-foreground"),
+    ("secondary", "secondary-foreground"),
+    ("muted", "muted-foreground"),
+    ("accent", "accent-foreground"),
+    ("destructive", "destructive-foreground"),
+];
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ThemeError {
+    /// The brand color is not a hex or OKLCH color.
+    InvalidColor(String),
+    /// The base color is not one of [`BASE_COLORS`].
+    UnknownBaseColor(String),
+}
+
+impl Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeError::InvalidColor(color) => write!(
+                f,
+                "Invalid color `{color}`. Use a hex color, e.g. `#2563eb`, or an OKLCH color, e.g. `oklch(0.546 0.245 262.881)`."
+            ),
+            ThemeError::UnknownBaseColor(base_color) => write!(
+                f,
+                "Unknown base color `{base_color}`. Use one of {}.",
+                BASE_COLORS
+                    .iter()
+                    .map(|base_color| format!("`{}`", base_color.name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+impl Error for ThemeError {}
+
+/// Parse a hex color, e.g. `#2563eb`, or an OKLCH color, e.g. `oklch(0.546 0.245 262.881)`.
+pub fn parse_color(color: &str) -> Result<Oklch, ThemeError> {
+    let parsed = if color.starts_with('#') {
+        Rgb::from_hex(color).map(Oklch::from)
+    } else {
+        color.parse::<Oklch>().ok()
+    };
+
+    parsed.ok_or_else(|| ThemeError::InvalidColor(color.into()))
+}
+
+/// Generate light and dark CSS variables from a brand color and a neutral base color.
+///
+/// Values are HSL channels, like the other themes. The primary color and ring are derived from the brand color and
+/// chart colors are spread around its hue. Foregrounds are adjusted where needed to meet [`WCAG_AA_CONTRAST`].
+pub fn generate_theme(
+    brand_color: &str,
+    base_color: &str,
+) -> Result<RegistryItemCssVars, ThemeError> {
+    let brand = parse_color(brand_color)?;
+    let base = BASE_COLORS
+        .iter()
+        .find(|base| base.name == base_color)
+        .ok_or_else(|| ThemeError::UnknownBaseColor(base_color.into()))?;
+
+    let mut css_vars = HashMap::new();
+    for mode in [Mode::Light, Mode::Dark] {
+        let mut vars = base.css_vars.get(&mode).cloned().unwrap_or_default();
+
+        let primary = match mode {
+            Mode::Light => brand,
+            // Brand colors are usually too dark on dark backgrounds.
+            Mode::Dark => Oklch {
+                l: brand.l.max(0.7),
+                ..brand
+            },
+        };
+        vars.insert("primary".into(), to_hsl_channel(primary));
+        vars.insert("ring".into(), to_hsl_channel(primary));
+
+        // Use the base's lightest or darkest neutral, whichever contrasts more.
+        let neutrals = [
+            vars.get("background").cloned().unwrap_or_default(),
+            vars.get("foreground").cloned().unwrap_or_default(),
+        ];
+        let primary_foreground = neutrals
+            .iter()
+            .filter_map(|neutral| Rgb::from_hsl_channel(neutral))
+            .max_by(|a, b| {
+                let primary = quantize(primary);
+                a.contrast(primary).total_cmp(&b.contrast(primary))
+            })
+            .map(Rgb::to_hsl_channel)
+            .unwrap_or_else(|| "0 0% 100%".into());
+        vars.insert("primary-foreground".into(), primary_foreground);
+
+        for (index, chart) in chart_colors(brand, mode).into_iter().enumerate() {
+            vars.insert(format!("chart-{}", index + 1), to_hsl_channel(chart));
+        }
+
+        for (background_key, foreground_key) in CONTRAST_PAIRS {
+            let (Some(background), Some(foreground)) = (
+                vars.get(background_key)
+                    .and_then(|value| Rgb::from_hsl_channel(value)),
+                vars.get(foreground_key)
+                    .and_then(|value| Rgb::from_hsl_channel(value)),
+            ) else {
+                continue;
+            };
+
+            vars.insert(
+                foreground_key.into(),
+                ensure_contrast(background, foreground),
+            );
+        }
+
+        css_vars.insert(mode, vars);
+    }
+
+    Ok(css_vars)
+}
+
+/// Five colors with the brand's chroma, spread evenly around the hue circle.
+fn chart_colors(brand: Oklch, mode: Mode) -> Vec<Oklch> {
+    let lightness = match mode {
+        Mode::Light => 0.65,
+        Mode::Dark => 0.7,
+    };
+    let chroma = brand.c.clamp(0.1, 0.2);
+
+    (0..5)
+        .map(|index| Oklch {
+            l: lightness,
+            c: chroma,
+            h: (brand.h + 72.0 * f64::from(index)).rem_euclid(360.0),
+        })
+        .collect()
+}
+
+/// Adjust the foreground's lightness until it meets [`WCAG_AA_CONTRAST`] against the background.
+///
+/// Contrast is checked on the rounded HSL channels that are written, so the result is guaranteed to pass.
+fn ensure_contrast(background: Rgb, foreground: Rgb) -> String {
+    let value = foreground.to_hsl_channel();
+    if quantize_channel(&value).contrast(background) >= WCAG_AA_CONTRAST {
+        return value;
+    }
+
+    // Move away from the background towards black or white, whichever contrasts more.
+    let black = Rgb {
+        r: 0.0,
+        g: 0.0,
+        b: 0.0,
+    };
+    let white = Rgb {
+        r: 1.0,
+        g: 1.0,
+        b: 1.0,
+    };
+    let (target, step) = if black.contrast(background) > white.contrast(background) {
+        (black, -0.01)
+    } else {
+        (white, 0.01)
+    };
+
+    let mut color = Oklch::from(foreground);
+    while (0.0..=1.0).contains(&color.l) {
+        let value = to_hsl_channel(color);
+        if quantize_channel(&value).contrast(background) >= WCAG_AA_CONTRAST {
+            return value;
+        }
+        color.l += step;
+    }
+
+    // Black or white always reach 4.5:1, as the product of their contrasts against any color is 21.
+    target.to_hsl_channel()
+}
+
+fn to_hsl_channel(color: Oklch) -> String {
+    Rgb::from(color).to_hsl_channel()
+}
+
+/// The color as it is written, after rounding to HSL channels.
+fn quantize(color: Oklch) -> Rgb {
+    quantize_channel(&to_hsl_channel(color))
+}
+
+fn quantize_channel(value: &str) -> Rgb {
+    Rgb::from_hsl_channel(value).expect("HSL channels should be valid.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn meets_wcag_aa() {
+        for brand_color in [
+            "#2563eb",
+            "#facc15",
+            "#16a34a",
+            "#ffffff",
+            "#000000",
+            "oklch(0.7 0.2 330)",
+        ] {
+            for base_color in BASE_COLORS.iter() {
+                let css_vars = generate_theme(brand_color, &base_color.name).unwrap();
+
+                for (mode, vars) in &css_vars {
+                    for (background, foreground) in CONTRAST_PAIRS {
+                        let contrast = Rgb::from_hsl_channel(&vars[background])
+                            .unwrap()
+                            .contrast(Rgb::from_hsl_channel(&vars[foreground]).unwrap());
+
+                        assert!(
+                            contrast >= WCAG_AA_CONTRAST,
+                            "{brand_color} on {} ({mode}): {background}/{foreground} has contrast {contrast:.2}",
+                            base_color.name
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn generates_complete_theme() {
+        let css_vars = generate_theme("#2563eb", "zinc").unwrap();
+
+        for mode in [Mode::Light, Mode::Dark] {
+            let vars = &css_vars[&mode];
+            for key in BASE_COLORS[0].css_vars[&mode].keys() {
+                assert!(vars.contains_key(key), "missing {key} ({mode})");
+            }
+            for index in 1..=5 {
+                assert!(vars.contains_key(&format!("chart-{index}")));
+            }
+            assert_eq!(vars["primary"], vars["ring"]);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        assert_eq!(
+            generate_theme("blue", "zinc"),
+            Err(ThemeError::InvalidColor("blue".into()))
+        );
+        assert_eq!(
+            generate_theme("#2563eb", "purple"),
+            Err(ThemeError::UnknownBaseColor("purple".into()))
+        );
+    }
+}
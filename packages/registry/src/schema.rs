@@ -86,6 +86,29 @@ pub enum RegistryItemType {
     Ui,
 }
 
+impl RegistryItemType {
+    /// The name of the type without the `registry:` prefix, e.g. `ui`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            RegistryItemType::Block => "block",
+            RegistryItemType::Component => "component",
+            RegistryItemType::Example => "example",
+            RegistryItemType::Hook => "hook",
+            RegistryItemType::Lib => "lib",
+            RegistryItemType::Page => "page",
+            RegistryItemType::Style => "style",
+            RegistryItemType::Theme => "theme",
+            RegistryItemType::Ui => "ui",
+        }
+    }
+}
+
+impl Display for RegistryItemType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "registry:{}", self.name())
+    }
+}
+
 #[serde_with::skip_serializing_none]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -114,6 +137,103 @@ pub struct RegistryItemTailwindConfig {
 
 pub type RegistryItemCssVars = HashMap<Mode, HashMap<String, String>>;
 
+/// A crate required by a registry item.
+///
+/// Either a string such as `tailwind_fuse`, `tailwind_fuse@0.3.0` or `lucide-leptos/notifications`, or a full [`CrateDependency`].
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum RegistryItemDependency {
+    Name(String),
+    Crate(CrateDependency),
+}
+
+impl RegistryItemDependency {
+    /// Normalize the dependency into a [`CrateDependency`].
+    pub fn to_crate(&self) -> CrateDependency {
+        match self {
+            RegistryItemDependency::Name(value) => {
+                let (name, feature) = match value.split_once('/') {
+                    Some((name, feature)) => (name, Some(feature)),
+                    None => (value.as_str(), None),
+                };
+                let (name, version) = match name.split_once('@') {
+                    Some((name, version)) => (name, Some(version)),
+                    None => (name, None),
+                };
+
+                CrateDependency {
+                    name: name.trim().into(),
+                    version: version.map(|version| version.trim().into()),
+                    features: feature.map(|feature| vec![feature.trim().into()]),
+                    ..Default::default()
+                }
+            }
+            RegistryItemDependency::Crate(dependency) => dependency.clone(),
+        }
+    }
+}
+
+impl From<&str> for RegistryItemDependency {
+    fn from(value: &str) -> Self {
+        RegistryItemDependency::Name(value.into())
+    }
+}
+
+impl From<CrateDependency> for RegistryItemDependency {
+    fn from(value: CrateDependency) -> Self {
+        RegistryItemDependency::Crate(value)
+    }
+}
+
+impl Display for RegistryItemDependency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryItemDependency::Name(value) => write!(f, "{value}"),
+            RegistryItemDependency::Crate(dependency) => write!(f, "{dependency}"),
+        }
+    }
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrateDependency {
+    pub name: String,
+    /// Version requirement, e.g. `0.3.0`.
+    pub version: Option<String>,
+    pub features: Option<Vec<String>>,
+    pub default_features: Option<bool>,
+    /// Git repository URL, used instead of crates.io.
+    pub git: Option<String>,
+    pub branch: Option<String>,
+    pub tag: Option<String>,
+    pub rev: Option<String>,
+}
+
+impl Display for CrateDependency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if let Some(version) = &self.version {
+            write!(f, "@{version}")?;
+        }
+        if let Some(git) = &self.git {
+            write!(f, " (git: {git})")?;
+        }
+        if let Some(features) = self
+            .features
+            .as_ref()
+            .filter(|features| !features.is_empty())
+        {
+            write!(f, " (features: {})", features.join(", "))?;
+        }
+        if self.default_features == Some(false) {
+            write!(f, " (no default features)")?;
+        }
+
+        Ok(())
+    }
+}
+
 #[serde_with::skip_serializing_none]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -121,8 +241,8 @@ pub struct RegistryEntry {
     pub name: String,
     pub r#type: RegistryItemType,
     pub description: Option<String>,
-    pub dependencies: Option<Vec<String>>,
-    pub dev_dependencies: Option<Vec<String>>,
+    pub dependencies: Option<Vec<RegistryItemDependency>>,
+    pub dev_dependencies: Option<Vec<RegistryItemDependency>>,
     pub registry_dependencies: Option<Vec<String>>,
     pub files: Option<Vec<RegistryItemFile>>,
     pub tailwind: Option<RegistryItemTailwind>,
@@ -136,6 +256,21 @@ pub struct RegistryEntry {
 
 pub type Registry = Vec<RegistryEntry>;
 
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistryBaseColor {
+    pub inline_colors: RegistryItemCssVars,
+    pub css_vars: RegistryItemCssVars,
+    pub inline_colors_template: String,
+    pub css_vars_template: String,
+    /// Tailwind CSS v4 variant of `inline_colors_template`, empty for registries built before v4 support.
+    #[serde(default)]
+    pub inline_colors_template_v4: String,
+    /// Tailwind CSS v4 variant of `css_vars_template`, empty for registries built before v4 support.
+    #[serde(default)]
+    pub css_vars_template_v4: String,
+}
+
 #[serde_with::skip_serializing_none]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -143,8 +278,8 @@ pub struct Block {
     pub name: String,
     pub r#type: RegistryItemType,
     pub description: Option<String>,
-    pub dependencies: Option<Vec<String>>,
-    pub dev_dependencies: Option<Vec<String>>,
+    pub dependencies: Option<Vec<RegistryItemDependency>>,
+    pub dev_dependencies: Option<Vec<RegistryItemDependency>>,
     pub registry_dependencies: Option<Vec<String>>,
     pub files: Option<Vec<RegistryItemFile>>,
     pub tailwind: Option<RegistryItemTailwind>,
@@ -198,3 +333,29 @@ pub struct Framework {
     pub label: String,
     pub detect_dependencies: Vec<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use serde::de::{IntoDeserializer, value::Error};
+
+    use super::*;
+
+    #[test]
+    fn names_item_types_as_serialized() {
+        for r#type in [
+            RegistryItemType::Block,
+            RegistryItemType::Component,
+            RegistryItemType::Example,
+            RegistryItemType::Hook,
+            RegistryItemType::Lib,
+            RegistryItemType::Page,
+            RegistryItemType::Style,
+            RegistryItemType::Theme,
+            RegistryItemType::Ui,
+        ] {
+            let deserializer = IntoDeserializer::<Error>::into_deserializer(r#type.to_string());
+
+            assert_eq!(RegistryItemType::deserialize(deserializer), Ok(r#type));
+        }
+    }
+}
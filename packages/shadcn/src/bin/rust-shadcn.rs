@@ -1,39 +1,88 @@
 use anyhow::Result;
-use clap::{Args, Parser, Subcommand};
-use shadcn::commands::init::{InitOptions, init};
+use clap::{Parser, Subcommand};
+use shadcn::commands::{
+    add::{AddOptions, add},
+    diff::{DiffOptions, diff},
+    init::{InitOptions, init},
+    list::{ListOptions, list},
+    remove::{RemoveOptions, remove},
+    search::{SearchOptions, search},
+    update::{UpdateOptions, update},
+    view::{ViewOptions, view},
+};
+use shadcn::plan::{print_plan, start_dry_run};
 
 #[derive(Parser)]
 #[command(version, propagate_version = true)]
 #[command(about = "add components and dependencies to your project")]
+#[command(
+    after_help = "Set REGISTRY_URL to use another registry, e.g. file://./dist for a registry built by build_registry to work offline. Relative directories are resolved against the project root."
+)]
 // #[command(subcommand_required = true)]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    #[arg(
+        long,
+        global = true,
+        help = "show the changes of init, add, update or remove without writing them."
+    )]
+    dry_run: bool,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     #[command(about = "add a component to your project")]
-    Add(AddArgs),
+    Add(AddOptions),
     #[command(about = "check for updates against the registry")]
-    Diff(DiffArgs),
+    Diff(DiffOptions),
     #[command(about = "initialize your project and install dependencies")]
     Init(InitOptions),
+    #[command(about = "list the items available in the registry")]
+    List(ListOptions),
+    #[command(about = "remove installed components from your project")]
+    Remove(RemoveOptions),
+    #[command(about = "search the registry for items")]
+    Search(SearchOptions),
+    #[command(about = "update installed components, merging local changes")]
+    Update(UpdateOptions),
+    #[command(about = "view items from the registry")]
+    View(ViewOptions),
 }
 
-#[derive(Args)]
-struct AddArgs {}
-
-#[derive(Args)]
-struct DiffArgs {}
-
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    // Only commands which write files have a plan.
+    let plan = match &cli.command {
+        Commands::Add(args) => Some((args.cwd.clone(), args.json)),
+        Commands::Init(args) => Some((args.cwd.clone(), args.json)),
+        Commands::Remove(args) => Some((args.cwd.clone(), args.json)),
+        Commands::Update(args) => Some((args.cwd.clone(), args.json)),
+        _ => None,
+    };
+    if let Some((cwd, json)) = &plan
+        && cli.dry_run
+    {
+        start_dry_run(cwd, *json);
+    }
+
     match cli.command {
-        Commands::Add(_args) => Ok(()),
-        Commands::Diff(_args) => Ok(()),
+        Commands::Add(args) => add(args).await,
+        Commands::Diff(args) => diff(args).await,
         Commands::Init(args) => init(args).await,
+        Commands::List(args) => list(args).await,
+        Commands::Remove(args) => remove(args).await,
+        Commands::Search(args) => search(args).await,
+        Commands::Update(args) => update(args).await,
+        Commands::View(args) => view(args).await,
+    }?;
+
+    if let Some((_, json)) = plan {
+        print_plan(json)?;
     }
+
+    Ok(())
 }
@@ -1,3 +1,8 @@
 pub mod add;
 pub mod diff;
 pub mod init;
+pub mod list;
+pub mod remove;
+pub mod search;
+pub mod update;
+pub mod view;
@@ -1 +1,215 @@
+use std::{collections::HashMap, path::PathBuf};
 
+use anyhow::{Result, bail};
+use clap::Args;
+use convert_case::{Case, Casing};
+use shadcn_registry::schema::{FrameworkName, RegistryItemType};
+
+use crate::{
+    commands::init::{InitOptions, run_init},
+    preflights::preflight_add::pre_flight_add,
+    utils::{
+        add_components::{AddComponentsOptions, add_components},
+        errors::ErrorType,
+        get_project_info::get_project_info,
+        highlighter::HIGHLIGHTER,
+        logger::LOGGER,
+        prompts::{is_interactive, prompt_confirm, prompt_multi_select},
+        registry::{get_registry_index, set_registry_config},
+    },
+};
+
+#[derive(Args)]
+pub struct AddOptions {
+    #[arg(help = "the components to add or a url to the component.")]
+    pub components: Vec<String>,
+
+    #[arg(short, long, help = "skip confirmation prompt.")]
+    pub yes: bool,
+
+    #[arg(short, long, help = "overwrite existing files.")]
+    pub overwrite: bool,
+
+    #[arg(
+        short,
+        long,
+        help = "the working directory. defaults to the current directory.",
+        default_value = "."
+    )]
+    pub cwd: PathBuf,
+
+    #[arg(short, long, help = "add all available components.")]
+    pub all: bool,
+
+    #[arg(short, long, help = "the path to add the component to.")]
+    pub path: Option<PathBuf>,
+
+    #[arg(short, long, help = "mute output.")]
+    pub silent: bool,
+
+    #[arg(
+        long,
+        value_name = "COMPONENT=ALIAS",
+        value_parser = parse_alias,
+        help = "rename the public identifiers of a component, e.g. button=FancyButton."
+    )]
+    pub alias: Vec<(String, String)>,
+
+    #[arg(
+        long,
+        requires = "dry_run",
+        help = "output the plan of a dry run as json."
+    )]
+    pub json: bool,
+}
+
+fn parse_alias(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((component, alias)) if !component.is_empty() && !alias.is_empty() => {
+            Ok((component.into(), alias.to_case(Case::Pascal)))
+        }
+        _ => Err("expected COMPONENT=ALIAS".into()),
+    }
+}
+
+pub async fn add(options: AddOptions) -> Result<()> {
+    let mut preflight = pre_flight_add(&options).await?;
+
+    if preflight
+        .errors
+        .remove(&ErrorType::MissingDirOrEmptyProject)
+        .unwrap_or_default()
+    {
+        LOGGER.r#break();
+        LOGGER.error(&format!(
+            "No {} found at {}.\nRun {} to create a new project first.",
+            HIGHLIGHTER.info("Cargo.toml"),
+            HIGHLIGHTER.info(&options.cwd.to_string_lossy()),
+            HIGHLIGHTER.info("init"),
+        ));
+        LOGGER.r#break();
+
+        bail!("");
+    }
+
+    // No components.toml file. Prompt the user to run init.
+    if preflight
+        .errors
+        .remove(&ErrorType::MissingConfig)
+        .unwrap_or_default()
+    {
+        let message = format!(
+            "You need to create a {} file to add components.",
+            HIGHLIGHTER.info("components.toml"),
+        );
+
+        if !is_interactive() {
+            LOGGER.r#break();
+            LOGGER.error(&format!(
+                "{message}\nRun the {} command to create one.",
+                HIGHLIGHTER.info("init"),
+            ));
+            LOGGER.r#break();
+
+            bail!("");
+        }
+
+        if !options.yes && !prompt_confirm(&format!("{message} Proceed?"), true)? {
+            LOGGER.r#break();
+
+            bail!("");
+        }
+
+        preflight.config = Some(
+            run_init(&InitOptions {
+                components: vec![],
+                yes: true,
+                defaults: false,
+                force: false,
+                cwd: options.cwd.clone(),
+                silent: options.silent,
+                src_dir: false,
+                framework: None,
+                json: options.json,
+                skip_preflight: false,
+            })
+            .await?,
+        );
+    }
+
+    let Some(config) = preflight.config else {
+        bail!(
+            "Failed to read config at {}.",
+            HIGHLIGHTER.info(&options.cwd.to_string_lossy())
+        );
+    };
+    set_registry_config(&config);
+
+    let project_info = get_project_info(&options.cwd).await?;
+    let Some(framework) = project_info.framework else {
+        LOGGER.r#break();
+        LOGGER.error(&format!(
+            "Could not detect a supported framework at {}.",
+            HIGHLIGHTER.info(&options.cwd.to_string_lossy())
+        ));
+        LOGGER.r#break();
+
+        bail!("");
+    };
+
+    let components = if options.all {
+        get_registry_index(framework.name)
+            .await?
+            .into_iter()
+            .filter(|entry| entry.r#type == RegistryItemType::Ui)
+            .map(|entry| entry.name)
+            .collect::<Vec<_>>()
+    } else if options.components.is_empty() && is_interactive() {
+        prompt_for_registry_components(framework.name).await?
+    } else {
+        options.components.clone()
+    };
+
+    if components.is_empty() {
+        LOGGER.warn("No components selected. Exiting.");
+
+        return Ok(());
+    }
+
+    add_components(
+        &components,
+        &config,
+        AddComponentsOptions {
+            framework: framework.name,
+            overwrite: options.overwrite,
+            silent: options.silent,
+            path: options.path.as_deref(),
+            aliases: &options.alias.iter().cloned().collect::<HashMap<_, _>>(),
+        },
+    )
+    .await
+}
+
+async fn prompt_for_registry_components(framework: FrameworkName) -> Result<Vec<String>> {
+    let registry_index = match get_registry_index(framework).await {
+        Ok(registry_index) => registry_index,
+        Err(error) => {
+            LOGGER.error("Failed to fetch registry index.");
+            LOGGER.error(&error.to_string());
+            bail!("");
+        }
+    };
+
+    let components = registry_index
+        .into_iter()
+        .filter(|entry| entry.r#type == RegistryItemType::Ui)
+        .map(|entry| entry.name)
+        .collect::<Vec<_>>();
+
+    let selected = prompt_multi_select("Which components would you like to add?", &components)?;
+
+    Ok(selected
+        .into_iter()
+        .map(|index| components[index].clone())
+        .collect())
+}
@@ -1 +1,394 @@
+use std::{collections::HashMap, path::PathBuf, slice};
 
+use anyhow::{Result, bail};
+use clap::Args;
+use shadcn_registry::schema::{FrameworkName, RegistryEntry};
+use similar::{ChangeTag, TextDiff};
+use tokio::fs;
+
+use crate::utils::{
+    errors::ErrorType,
+    get_config::{CONFIG_FILE_NAME, Config, get_config},
+    get_project_info::get_project_info,
+    highlighter::HIGHLIGHTER,
+    lockfile::{
+        LOCKFILE_NAME, Lockfile, LockfileMismatch, get_installed_file_path, read_lockfile,
+        verify_lockfile,
+    },
+    logger::LOGGER,
+    plan::{path_exists, read_file},
+    registry::{
+        get_registry_base_color, get_registry_index, get_registry_item,
+        get_registry_item_file_path, set_registry_config,
+    },
+    transformers::{TransformOptions, transform_alias::get_alias_renames, transform_items},
+};
+
+#[derive(Args)]
+pub struct DiffOptions {
+    #[arg(help = "the component name.")]
+    pub component: Option<String>,
+
+    #[arg(
+        long,
+        help = "check installed files against the hashes in components.lock without fetching the registry.",
+        conflicts_with = "component"
+    )]
+    pub locked: bool,
+
+    #[arg(
+        short,
+        long,
+        help = "the working directory. defaults to the current directory.",
+        default_value = "."
+    )]
+    pub cwd: PathBuf,
+}
+
+pub async fn diff(options: DiffOptions) -> Result<()> {
+    if !fs::try_exists(&options.cwd).await? {
+        LOGGER.error(&format!(
+            "The path {} does not exist. Please try again.",
+            HIGHLIGHTER.info(&options.cwd.to_string_lossy())
+        ));
+        bail!("");
+    }
+
+    let config = match get_config(&options.cwd).await {
+        Ok(config) => config,
+        Err(error) => {
+            match error.downcast_ref::<ErrorType>() {
+                Some(ErrorType::MissingConfig) => LOGGER.warn(&format!(
+                    "Configuration is missing. Please run {} to create a {} file.",
+                    HIGHLIGHTER.success("init"),
+                    HIGHLIGHTER.info(CONFIG_FILE_NAME),
+                )),
+                _ => {
+                    LOGGER.error(&error.to_string());
+                    LOGGER.error(&error.root_cause().to_string());
+                }
+            }
+            bail!("");
+        }
+    };
+    set_registry_config(&config);
+
+    if options.locked {
+        return verify_locked_files(&config).await;
+    }
+
+    let project_info = get_project_info(&options.cwd).await?;
+    let Some(framework) = project_info.framework else {
+        LOGGER.error(&format!(
+            "Could not detect a supported framework at {}.",
+            HIGHLIGHTER.info(&options.cwd.to_string_lossy())
+        ));
+        bail!("");
+    };
+
+    let registry_index = match get_registry_index(framework.name).await {
+        Ok(registry_index) => registry_index,
+        Err(error) => {
+            LOGGER.error("Failed to fetch registry index.");
+            LOGGER.error(&error.to_string());
+            bail!("");
+        }
+    };
+
+    let lockfile = read_lockfile(&config).await?;
+
+    let Some(name) = &options.component else {
+        let project_components =
+            get_installed_components(&registry_index, &lockfile, &config).await?;
+
+        // Check for updates.
+        let mut components_with_updates = vec![];
+        for component in project_components {
+            let changes =
+                fetch_component_diff(component, &lockfile, &config, framework.name).await?;
+            if !changes.is_empty() {
+                components_with_updates.push((&component.name, changes));
+            }
+        }
+
+        if components_with_updates.is_empty() {
+            LOGGER.info("No updates found.");
+            return Ok(());
+        }
+
+        LOGGER.info("The following components have updates available:");
+        for (name, changes) in components_with_updates {
+            LOGGER.info(&format!("- {name}"));
+            for change in changes {
+                LOGGER.info(&format!("  - {}", change.file_path.display()));
+            }
+        }
+        LOGGER.r#break();
+        LOGGER.info(&format!(
+            "Run {} to see the changes.",
+            HIGHLIGHTER.success("diff <component>")
+        ));
+
+        return Ok(());
+    };
+
+    // Show diff for a single component.
+    let Some(component) = registry_index.iter().find(|item| &item.name == name) else {
+        LOGGER.error(&format!(
+            "The component {} does not exist.",
+            HIGHLIGHTER.success(name)
+        ));
+        bail!("");
+    };
+
+    let changes = fetch_component_diff(component, &lockfile, &config, framework.name).await?;
+    if changes.is_empty() {
+        LOGGER.info(&format!("No updates found for {name}."));
+        return Ok(());
+    }
+
+    for change in changes {
+        LOGGER.info(&format!("- {}", change.file_path.display()));
+        print_diff(&change.registry_content, &change.file_content);
+        LOGGER.r#break();
+    }
+
+    Ok(())
+}
+
+/// Check that the installed files match the lockfile, e.g. in CI.
+async fn verify_locked_files(config: &Config) -> Result<()> {
+    let lockfile = read_lockfile(config).await?;
+    let mismatches = verify_lockfile(&lockfile, config).await?;
+    if mismatches.is_empty() {
+        LOGGER.info(&format!(
+            "All files match {}.",
+            HIGHLIGHTER.info(LOCKFILE_NAME)
+        ));
+        return Ok(());
+    }
+
+    for mismatch in mismatches {
+        match mismatch {
+            LockfileMismatch::Missing(path) => {
+                LOGGER.error(&format!("The file {} is missing.", HIGHLIGHTER.info(&path)))
+            }
+            LockfileMismatch::Modified(path) => LOGGER.error(&format!(
+                "The file {} was modified locally.",
+                HIGHLIGHTER.info(&path)
+            )),
+        }
+    }
+    bail!("");
+}
+
+struct Change {
+    file_path: PathBuf,
+    registry_content: String,
+    file_content: String,
+}
+
+async fn fetch_component_diff(
+    component: &RegistryEntry,
+    lockfile: &Lockfile,
+    config: &Config,
+    framework: FrameworkName,
+) -> Result<Vec<Change>> {
+    match diff_component(component, lockfile, config, framework).await {
+        Ok(changes) => Ok(changes),
+        Err(error) => {
+            LOGGER.r#break();
+            LOGGER.error(&format!(
+                "Failed to fetch {} from registry.",
+                HIGHLIGHTER.info(&component.name)
+            ));
+            LOGGER.error(&error.to_string());
+            LOGGER.r#break();
+
+            bail!("");
+        }
+    }
+}
+
+async fn diff_component(
+    component: &RegistryEntry,
+    lockfile: &Lockfile,
+    config: &Config,
+    framework: FrameworkName,
+) -> Result<Vec<Change>> {
+    let locked_item = lockfile.get(&component.name);
+    let payload =
+        get_transformed_registry_item(&component.name, &lockfile.aliases(), config, framework)
+            .await?;
+
+    let mut changes = vec![];
+    for file in payload.files.into_iter().flatten() {
+        let file_path = get_installed_file_path(&file, locked_item, config);
+        if !path_exists(&file_path).await? {
+            continue;
+        }
+
+        let Some(registry_content) = file.content else {
+            continue;
+        };
+        let file_content = read_file(&file_path).await?;
+
+        if registry_content != file_content {
+            changes.push(Change {
+                file_path: file_path
+                    .strip_prefix(&config.resolved_paths.cwd)
+                    .unwrap_or(&file_path)
+                    .to_path_buf(),
+                registry_content,
+                file_content,
+            });
+        }
+    }
+
+    Ok(changes)
+}
+
+/// Find the installed components, from the lockfile or else by looking for their files.
+pub async fn get_installed_components<'a>(
+    registry_index: &'a [RegistryEntry],
+    lockfile: &Lockfile,
+    config: &Config,
+) -> Result<Vec<&'a RegistryEntry>> {
+    if !lockfile.items.is_empty() {
+        return Ok(registry_index
+            .iter()
+            .filter(|item| lockfile.get(&item.name).is_some())
+            .collect());
+    }
+
+    let mut components = vec![];
+    for item in registry_index {
+        for file in item.files.iter().flatten() {
+            if path_exists(&get_registry_item_file_path(file, config, None)).await? {
+                components.push(item);
+                break;
+            }
+        }
+    }
+
+    Ok(components)
+}
+
+/// Fetch a registry item with its files transformed as they would be installed.
+///
+/// `aliases` are the aliases of the installed items, see [`Lockfile::aliases`]. Identifiers of the item and its
+/// aliased registry dependencies are renamed.
+pub async fn get_transformed_registry_item(
+    name: &str,
+    aliases: &HashMap<String, String>,
+    config: &Config,
+    framework: FrameworkName,
+) -> Result<RegistryEntry> {
+    let mut payload = get_registry_item(name, framework, config.style).await?;
+    let base_color = if config.tailwind.css_variables {
+        None
+    } else {
+        Some(get_registry_base_color(&config.tailwind.base_color).await?)
+    };
+
+    let mut aliased_items = vec![];
+    for dependency in payload.registry_dependencies.iter().flatten() {
+        if aliases.contains_key(dependency) {
+            aliased_items.push(get_registry_item(dependency, framework, config.style).await?);
+        }
+    }
+    aliased_items.push(payload.clone());
+    let renames = get_alias_renames(&aliased_items, aliases);
+    transform_items(
+        slice::from_mut(&mut payload),
+        &TransformOptions {
+            config,
+            framework,
+            base_color: base_color.as_ref(),
+            renames: &renames,
+        },
+    );
+
+    Ok(payload)
+}
+
+fn print_diff(old: &str, new: &str) {
+    let diff = TextDiff::from_lines(old, new);
+
+    for hunk in diff.unified_diff().context_radius(3).iter_hunks() {
+        LOGGER.log(&HIGHLIGHTER.info(&hunk.header().to_string()));
+
+        for change in hunk.iter_changes() {
+            let line = format!("{}{}", change.tag(), change.value().trim_end_matches('\n'));
+            match change.tag() {
+                ChangeTag::Insert => LOGGER.log(&HIGHLIGHTER.success(&line)),
+                ChangeTag::Delete => LOGGER.log(&HIGHLIGHTER.error(&line)),
+                ChangeTag::Equal => LOGGER.log(&line),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::utils::{
+        add_components::{AddComponentsOptions, add_components},
+        test_utils::{TempDir, leptos_project, registry_item, write_registry},
+    };
+
+    #[tokio::test]
+    async fn diffs_lib_items() {
+        let dir = TempDir::new();
+        let utils = registry_item("utils", "lib", &[("lib/utils.rs", "pub fn cn() {}\n")], &[]);
+        let config = leptos_project(&dir, &[utils]).await;
+        add_components(
+            &["utils".into()],
+            &config,
+            AddComponentsOptions {
+                framework: FrameworkName::Leptos,
+                overwrite: false,
+                silent: true,
+                path: None,
+                aliases: &HashMap::new(),
+            },
+        )
+        .await
+        .unwrap();
+
+        let options = |component: Option<&str>| DiffOptions {
+            component: component.map(String::from),
+            locked: false,
+            cwd: dir.path().to_path_buf(),
+        };
+        diff(options(None)).await.unwrap();
+        diff(options(Some("utils"))).await.unwrap();
+
+        let utils = registry_item(
+            "utils",
+            "lib",
+            &[("lib/utils.rs", "pub fn cn() -> String {}\n")],
+            &[],
+        );
+        write_registry(&dir, &[utils]);
+        let registry_index = get_registry_index(FrameworkName::Leptos).await.unwrap();
+        let lockfile = read_lockfile(&config).await.unwrap();
+        let components = get_installed_components(&registry_index, &lockfile, &config)
+            .await
+            .unwrap();
+        assert_eq!(components.len(), 1);
+
+        let changes = diff_component(components[0], &lockfile, &config, FrameworkName::Leptos)
+            .await
+            .unwrap();
+        assert_eq!(
+            changes
+                .iter()
+                .map(|change| change.file_path.as_path())
+                .collect::<Vec<_>>(),
+            [Path::new("src/lib/utils.rs")]
+        );
+    }
+}
@@ -1,11 +1,31 @@
-use std::{env, path::PathBuf};
+use std::{collections::HashMap, env, path::PathBuf};
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use clap::Args;
+use shadcn_registry::{
+    registry_base_colors::BASE_COLORS, registry_lib::LIB, registry_styles::STYLES,
+    schema::FrameworkName,
+};
 
 use crate::{
     preflights::preflight_init::pre_flight_init,
-    utils::{errors::ErrorType, get_project_info::get_project_info, logger::LOGGER},
+    utils::{
+        add_components::{AddComponentsOptions, add_components},
+        create_project::{CreateProjectOptions, create_project, parse_framework_name},
+        errors::ErrorType,
+        get_config::{
+            CONFIG_FILE_NAME, Config, DEFAULT_STYLE, DEFAULT_TAILWIND_BASE_COLOR,
+            DEFAULT_TAILWIND_CONFIG, DEFAULT_TAILWIND_CSS, PathsConfig, RawConfig, RegistryConfig,
+            TailwindConfig, get_raw_config, resolve_config_paths, write_config,
+        },
+        get_project_info::{ProjectInfo, TailwindVersion, get_project_info},
+        highlighter::HIGHLIGHTER,
+        logger::LOGGER,
+        prompts::{is_interactive, prompt_confirm, prompt_select},
+        registry::{get_registry_base_color, set_registry_config},
+        spinner::{SpinnerOptions, spinner},
+        updaters::update_css_vars::{UpdateCssVarsOptions, update_css_vars},
+    },
 };
 
 fn _default_cwd() -> PathBuf {
@@ -40,11 +60,34 @@ pub struct InitOptions {
     #[arg(long, help = "use the src directory when creating a new project.")]
     pub src_dir: bool,
 
+    #[arg(
+        long,
+        help = "the framework to use when creating a new project.",
+        value_parser = parse_framework_name
+    )]
+    pub framework: Option<FrameworkName>,
+
+    #[arg(
+        long,
+        requires = "dry_run",
+        help = "output the plan of a dry run as json."
+    )]
+    pub json: bool,
+
     #[arg(skip)]
     pub skip_preflight: bool,
 }
 
 pub async fn init(options: InitOptions) -> Result<()> {
+    run_init(&options).await?;
+
+    LOGGER.info("Success! Project initialization completed.\nYou may now add components.");
+    LOGGER.r#break();
+
+    Ok(())
+}
+
+pub async fn run_init(options: &InitOptions) -> Result<Config> {
     let project_info = if !options.skip_preflight {
         let mut preflight = pre_flight_init(options).await?;
         if preflight
@@ -52,17 +95,218 @@ pub async fn init(options: InitOptions) -> Result<()> {
             .remove(&ErrorType::MissingDirOrEmptyProject)
             .unwrap_or_default()
         {
-            // TODO: create project
+            create_project(
+                &options.cwd,
+                CreateProjectOptions {
+                    framework: options.framework,
+                    force: options.force,
+                    silent: options.silent,
+                    src_dir: options.src_dir,
+                    yes: options.yes,
+                },
+            )
+            .await?;
+
+            Some(get_project_info(&options.cwd).await?)
+        } else {
+            preflight.project_info
         }
-        preflight.project_info
     } else {
         Some(get_project_info(&options.cwd).await?)
     };
 
-    // TODO
+    let Some(project_info) = project_info else {
+        bail!(
+            "Failed to get project information for {}.",
+            options.cwd.display()
+        );
+    };
+    let Some(framework) = &project_info.framework else {
+        LOGGER.r#break();
+        LOGGER.error(&format!(
+            "We could not detect a supported framework at {}.",
+            HIGHLIGHTER.info(&options.cwd.to_string_lossy())
+        ));
+        LOGGER.r#break();
 
-    LOGGER.info("Success! Project initialization completed.\nYou may now add components.");
-    LOGGER.r#break();
+        bail!("");
+    };
 
-    Ok(())
+    let existing_config = get_raw_config(&options.cwd).await.ok();
+    let mut config = resolve_init_config(&project_info, existing_config);
+
+    if is_interactive() {
+        if !options.defaults {
+            config = prompt_for_config(config)?;
+        }
+
+        if !options.yes
+            && !prompt_confirm(
+                &format!(
+                    "Write configuration to {}. Proceed?",
+                    HIGHLIGHTER.info(CONFIG_FILE_NAME)
+                ),
+                true,
+            )?
+        {
+            bail!("");
+        }
+    }
+
+    // Write components.toml.
+    let mut components_spinner = spinner(
+        format!("Writing {}.", CONFIG_FILE_NAME),
+        SpinnerOptions {
+            silent: options.silent,
+        },
+    );
+    write_config(&options.cwd, &config).await?;
+    components_spinner.succeed(None);
+
+    let config = resolve_config_paths(&options.cwd, config).await?;
+    set_registry_config(&config);
+
+    // Write Tailwind CSS variables.
+    let base_color = match get_registry_base_color(&config.tailwind.base_color).await {
+        Ok(base_color) => base_color,
+        Err(error) => {
+            LOGGER.r#break();
+            LOGGER.error(&format!(
+                "Failed to fetch base color {} from registry.",
+                HIGHLIGHTER.info(&config.tailwind.base_color)
+            ));
+            LOGGER.error(&error.to_string());
+            LOGGER.r#break();
+
+            bail!("");
+        }
+    };
+    update_css_vars(
+        &base_color,
+        &config,
+        UpdateCssVarsOptions {
+            silent: options.silent,
+            tailwind_version: project_info.tailwind_version,
+        },
+    )
+    .await?;
+
+    // Add utils and components.
+    let mut components = LIB
+        .get(&framework.name)
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.name == "utils")
+        .map(|entry| entry.name.clone())
+        .collect::<Vec<_>>();
+    components.extend(options.components.iter().cloned());
+
+    if !components.is_empty() {
+        add_components(
+            &components,
+            &config,
+            AddComponentsOptions {
+                framework: framework.name,
+                overwrite: true,
+                silent: options.silent,
+                path: None,
+                aliases: &HashMap::new(),
+            },
+        )
+        .await?;
+    }
+
+    Ok(config)
+}
+
+/// Determine the configuration to write, preferring values from an existing configuration.
+fn resolve_init_config(
+    project_info: &ProjectInfo,
+    existing_config: Option<RawConfig>,
+) -> RawConfig {
+    // Tailwind CSS v4 is configured in CSS, so there may be no config file.
+    let tailwind_config = match (
+        &project_info.tailwind_config_file,
+        project_info.tailwind_version,
+    ) {
+        (Some(path), _) => path.to_string_lossy().to_string(),
+        (None, TailwindVersion::V3) => DEFAULT_TAILWIND_CONFIG.into(),
+        (None, TailwindVersion::V4) => "".into(),
+    };
+    let tailwind_css = project_info
+        .tailwind_css_file
+        .as_ref()
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or_else(|| DEFAULT_TAILWIND_CSS.into());
+
+    match existing_config {
+        Some(existing_config) => RawConfig {
+            tailwind: TailwindConfig {
+                config: tailwind_config,
+                css: tailwind_css,
+                ..existing_config.tailwind
+            },
+            ..existing_config
+        },
+        None => RawConfig {
+            style: DEFAULT_STYLE,
+            tailwind: TailwindConfig {
+                config: tailwind_config,
+                css: tailwind_css,
+                base_color: DEFAULT_TAILWIND_BASE_COLOR.into(),
+                css_variables: true,
+                prefix: "".into(),
+            },
+            paths: PathsConfig::default(),
+            registry: RegistryConfig::default(),
+        },
+    }
+}
+
+/// Ask for the style, base color and CSS variables, defaulting to the values of `config`.
+fn prompt_for_config(config: RawConfig) -> Result<RawConfig> {
+    let style = STYLES[prompt_select(
+        &format!("Which {} would you like to use?", HIGHLIGHTER.info("style")),
+        &STYLES.iter().map(|style| style.label).collect::<Vec<_>>(),
+        STYLES
+            .iter()
+            .position(|style| style.name == config.style)
+            .unwrap_or_default(),
+    )?]
+    .name;
+
+    let base_color = BASE_COLORS[prompt_select(
+        &format!(
+            "Which color would you like to use as the {}?",
+            HIGHLIGHTER.info("base color")
+        ),
+        &BASE_COLORS
+            .iter()
+            .map(|base_color| base_color.label.as_str())
+            .collect::<Vec<_>>(),
+        BASE_COLORS
+            .iter()
+            .position(|base_color| base_color.name == config.tailwind.base_color)
+            .unwrap_or_default(),
+    )?]
+    .name
+    .clone();
+
+    let css_variables = prompt_confirm(
+        &format!(
+            "Would you like to use {} for theming?",
+            HIGHLIGHTER.info("CSS variables")
+        ),
+        config.tailwind.css_variables,
+    )?;
+
+    Ok(RawConfig {
+        style,
+        tailwind: TailwindConfig {
+            base_color,
+            css_variables,
+            ..config.tailwind
+        },
+        ..config
+    })
 }
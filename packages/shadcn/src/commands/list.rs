@@ -0,0 +1,175 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, bail};
+use clap::Args;
+use serde::Serialize;
+use shadcn_registry::{
+    REGISTRY,
+    schema::{FrameworkName, RegistryEntry, RegistryItemType},
+};
+use tokio::fs;
+
+use crate::utils::{
+    errors::ErrorType,
+    get_config::{Config, get_config},
+    get_project_info::get_project_info,
+    highlighter::HIGHLIGHTER,
+    lockfile::{Lockfile, read_lockfile},
+    logger::LOGGER,
+    registry::get_registry_item_file_path,
+};
+
+/// Item types shown by `list` and `search`, in display order.
+const LIST_ITEM_TYPES: [RegistryItemType; 5] = [
+    RegistryItemType::Ui,
+    RegistryItemType::Lib,
+    RegistryItemType::Hook,
+    RegistryItemType::Block,
+    RegistryItemType::Theme,
+];
+
+#[derive(Args)]
+pub struct ListOptions {
+    #[arg(
+        short,
+        long,
+        help = "the working directory. defaults to the current directory.",
+        default_value = "."
+    )]
+    pub cwd: PathBuf,
+
+    #[arg(long, help = "output as json.")]
+    pub json: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListItem {
+    pub name: String,
+    pub r#type: RegistryItemType,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub installed: bool,
+}
+
+pub async fn list(options: ListOptions) -> Result<()> {
+    let items = get_list_items(&options.cwd, |_| true).await?;
+
+    print_list_items(&items, options.json)
+}
+
+/// Collect the registry items for the framework detected in `cwd` which match `filter`.
+pub async fn get_list_items<F>(cwd: &Path, filter: F) -> Result<Vec<ListItem>>
+where
+    F: Fn(&RegistryEntry) -> bool,
+{
+    if !fs::try_exists(cwd).await? {
+        LOGGER.error(&format!(
+            "The path {} does not exist. Please try again.",
+            HIGHLIGHTER.info(&cwd.to_string_lossy())
+        ));
+        bail!("");
+    }
+
+    let project_info = get_project_info(cwd).await?;
+    let Some(framework) = project_info.framework else {
+        LOGGER.error(&format!(
+            "Could not detect a supported framework at {}.",
+            HIGHLIGHTER.info(&cwd.to_string_lossy())
+        ));
+        bail!("");
+    };
+
+    // Installed status is only available once the project is initialized.
+    let config = match get_config(cwd).await {
+        Ok(config) => {
+            let lockfile = read_lockfile(&config).await?;
+            Some((config, lockfile))
+        }
+        Err(error) => match error.downcast_ref::<ErrorType>() {
+            Some(ErrorType::MissingConfig) => None,
+            _ => {
+                LOGGER.error(&error.to_string());
+                LOGGER.error(&error.root_cause().to_string());
+                bail!("");
+            }
+        },
+    };
+
+    let mut items = vec![];
+    for r#type in LIST_ITEM_TYPES {
+        for entry in registry_entries(framework.name)
+            .filter(|entry| entry.r#type == r#type)
+            .filter(|entry| filter(entry))
+        {
+            items.push(ListItem {
+                name: entry.name.clone(),
+                r#type: entry.r#type,
+                description: entry.description.clone(),
+                category: entry.category.clone(),
+                installed: match &config {
+                    Some((config, lockfile)) => is_installed(entry, lockfile, config).await?,
+                    None => false,
+                },
+            });
+        }
+    }
+
+    Ok(items)
+}
+
+pub fn print_list_items(items: &[ListItem], json: bool) -> Result<()> {
+    if json {
+        LOGGER.log(&serde_json::to_string_pretty(items)?);
+        return Ok(());
+    }
+
+    if items.is_empty() {
+        LOGGER.info("No items found.");
+        return Ok(());
+    }
+
+    for r#type in LIST_ITEM_TYPES {
+        let group = items
+            .iter()
+            .filter(|item| item.r#type == r#type)
+            .collect::<Vec<_>>();
+        if group.is_empty() {
+            continue;
+        }
+
+        LOGGER.log(&HIGHLIGHTER.info(r#type.name()));
+        for item in group {
+            let mut line = format!("  - {}", item.name);
+            if let Some(description) = &item.description {
+                line.push_str(&format!(": {description}"));
+            }
+            if item.installed {
+                line.push_str(&format!(" {}", HIGHLIGHTER.success("(installed)")));
+            }
+            LOGGER.log(&line);
+        }
+        LOGGER.r#break();
+    }
+
+    Ok(())
+}
+
+fn registry_entries(framework: FrameworkName) -> impl Iterator<Item = &'static RegistryEntry> {
+    REGISTRY.get(&framework).into_iter().flatten()
+}
+
+/// Whether the item is recorded in the lockfile, or any of its files exist for projects without one.
+async fn is_installed(entry: &RegistryEntry, lockfile: &Lockfile, config: &Config) -> Result<bool> {
+    if lockfile.get(&entry.name).is_some() {
+        return Ok(true);
+    }
+
+    for file in entry.files.iter().flatten() {
+        if fs::try_exists(get_registry_item_file_path(file, config, None)).await? {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
@@ -0,0 +1,428 @@
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Result, bail};
+use clap::Args;
+use regex::Regex;
+use tokio::fs;
+
+use crate::utils::{
+    base_files::get_base_file_path,
+    errors::ErrorType,
+    get_config::{CONFIG_FILE_NAME, get_config},
+    highlighter::HIGHLIGHTER,
+    lockfile::{LOCKFILE_NAME, LockedItem, hash_content, read_lockfile, write_lockfile},
+    logger::LOGGER,
+    modules::remove_module_declaration,
+    plan::{is_dry_run, path_exists, read_file, remove_file},
+    prompts::{is_interactive, prompt_confirm},
+    spinner::{SpinnerOptions, spinner},
+    updaters::update_dependencies::{UpdateDependenciesOptions, remove_dependencies},
+};
+
+#[derive(Args)]
+pub struct RemoveOptions {
+    #[arg(required = true, help = "the components to remove.")]
+    pub components: Vec<String>,
+
+    #[arg(short, long, help = "skip confirmation prompt.")]
+    pub yes: bool,
+
+    #[arg(
+        short,
+        long,
+        help = "remove components which are depended on or modified locally."
+    )]
+    pub force: bool,
+
+    #[arg(
+        short,
+        long,
+        help = "the working directory. defaults to the current directory.",
+        default_value = "."
+    )]
+    pub cwd: PathBuf,
+
+    #[arg(short, long, help = "mute output.")]
+    pub silent: bool,
+
+    #[arg(
+        long,
+        requires = "dry_run",
+        help = "output the plan of a dry run as json."
+    )]
+    pub json: bool,
+}
+
+pub async fn remove(options: RemoveOptions) -> Result<()> {
+    if !fs::try_exists(&options.cwd).await? {
+        LOGGER.error(&format!(
+            "The path {} does not exist. Please try again.",
+            HIGHLIGHTER.info(&options.cwd.to_string_lossy())
+        ));
+        bail!("");
+    }
+
+    let config = match get_config(&options.cwd).await {
+        Ok(config) => config,
+        Err(error) => {
+            match error.downcast_ref::<ErrorType>() {
+                Some(ErrorType::MissingConfig) => LOGGER.warn(&format!(
+                    "Configuration is missing. Please run {} to create a {} file.",
+                    HIGHLIGHTER.success("init"),
+                    HIGHLIGHTER.info(CONFIG_FILE_NAME),
+                )),
+                _ => {
+                    LOGGER.error(&error.to_string());
+                    LOGGER.error(&error.root_cause().to_string());
+                }
+            }
+            bail!("");
+        }
+    };
+
+    let mut lockfile = read_lockfile(&config).await?;
+
+    let mut targets: Vec<LockedItem> = vec![];
+    for name in &options.components {
+        let Some(item) = lockfile.get(name) else {
+            LOGGER.error(&format!(
+                "The component {} is not installed according to {}.",
+                HIGHLIGHTER.success(name),
+                HIGHLIGHTER.info(LOCKFILE_NAME)
+            ));
+            bail!("");
+        };
+        if !targets.iter().any(|target| &target.name == name) {
+            targets.push(item.clone());
+        }
+    }
+    let is_target = |name: &str| targets.iter().any(|target| target.name == name);
+
+    // Components which are still needed by other installed components.
+    let dependents = lockfile
+        .items
+        .iter()
+        .filter(|item| !is_target(&item.name))
+        .flat_map(|item| {
+            item.registry_dependencies
+                .iter()
+                .filter(|dependency| is_target(dependency))
+                .map(|dependency| (dependency.clone(), item.name.clone()))
+        })
+        .collect::<Vec<_>>();
+
+    // Files which differ from the version installed from the registry.
+    let mut modified_files: Vec<String> = vec![];
+    for file in targets.iter().flat_map(|target| &target.files) {
+        let file_path = config.resolved_paths.cwd.join(&file.path);
+        if !fs::try_exists(&file_path).await? {
+            continue;
+        }
+
+        let content = fs::read_to_string(&file_path).await?;
+        if hash_content(&content) != file.hash {
+            modified_files.push(file.path.clone());
+        }
+    }
+
+    if !options.force && (!dependents.is_empty() || !modified_files.is_empty()) {
+        LOGGER.r#break();
+        for (dependency, dependent) in &dependents {
+            LOGGER.error(&format!(
+                "The component {} is used by {}.",
+                HIGHLIGHTER.success(dependency),
+                HIGHLIGHTER.success(dependent)
+            ));
+        }
+        for path in &modified_files {
+            LOGGER.error(&format!(
+                "The file {} was modified locally.",
+                HIGHLIGHTER.info(path)
+            ));
+        }
+        LOGGER.error(&format!(
+            "Use {} to remove the components anyway.",
+            HIGHLIGHTER.info("--force")
+        ));
+        LOGGER.r#break();
+
+        bail!("");
+    }
+
+    if !options.yes
+        && !is_dry_run()
+        && is_interactive()
+        && !prompt_confirm(
+            &format!(
+                "Remove {}? Their files will be deleted.",
+                targets
+                    .iter()
+                    .map(|target| HIGHLIGHTER.success(&target.name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            true,
+        )?
+    {
+        LOGGER.r#break();
+
+        bail!("");
+    }
+
+    for target in &targets {
+        lockfile.remove(&target.name);
+    }
+
+    let mut files_spinner = spinner(
+        "Removing files.",
+        SpinnerOptions {
+            silent: options.silent,
+        },
+    );
+    let mut files_removed: Vec<PathBuf> = vec![];
+    let mut modules_updated: Vec<PathBuf> = vec![];
+
+    for file in targets.iter().flat_map(|target| &target.files) {
+        // Files can be shared with components which remain installed.
+        if lockfile
+            .items
+            .iter()
+            .flat_map(|item| &item.files)
+            .any(|remaining| remaining.path == file.path)
+        {
+            continue;
+        }
+
+        let file_path = config.resolved_paths.cwd.join(&file.path);
+        if path_exists(&file_path).await? {
+            remove_file(&file_path).await?;
+            files_removed.push(PathBuf::from(&file.path));
+        }
+
+        let base_path = get_base_file_path(&file_path, &config);
+        if path_exists(&base_path).await? {
+            remove_file(&base_path).await?;
+        }
+
+        if let Some(module_path) = remove_module_declaration(&file_path, &config).await? {
+            let module_path = module_path
+                .strip_prefix(&config.resolved_paths.cwd)
+                .unwrap_or(&module_path)
+                .to_path_buf();
+            if !modules_updated.contains(&module_path) {
+                modules_updated.push(module_path);
+            }
+        }
+    }
+    files_spinner.succeed(None);
+
+    // Crates which no remaining component needs and the project does not use directly.
+    let sources = read_rust_sources(&config.resolved_paths.cwd.join("src")).await?;
+    let needed = lockfile
+        .items
+        .iter()
+        .flat_map(|item| &item.dependencies)
+        .collect::<BTreeSet<_>>();
+    let unused = targets
+        .iter()
+        .flat_map(|target| &target.dependencies)
+        .filter(|dependency| !needed.contains(dependency))
+        .filter(|dependency| !is_crate_used(dependency, &sources))
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    let dependencies_removed = remove_dependencies(
+        &unused,
+        &config,
+        UpdateDependenciesOptions {
+            silent: options.silent,
+        },
+    )
+    .await?;
+
+    write_lockfile(&config, &lockfile).await?;
+
+    if options.silent {
+        return Ok(());
+    }
+
+    for (label, paths, unit) in [
+        ("Removed", &files_removed, "file"),
+        ("Updated", &modules_updated, "module"),
+    ] {
+        if paths.is_empty() {
+            continue;
+        }
+
+        LOGGER.log(&format!(
+            "{} {} {}{}:",
+            label,
+            paths.len(),
+            unit,
+            if paths.len() == 1 { "" } else { "s" }
+        ));
+        for path in paths {
+            LOGGER.log(&format!("  - {}", path.display()));
+        }
+    }
+
+    if !dependencies_removed.is_empty() {
+        LOGGER.log(&format!(
+            "Removed {} {} from Cargo.toml:",
+            dependencies_removed.len(),
+            if dependencies_removed.len() == 1 {
+                "crate"
+            } else {
+                "crates"
+            }
+        ));
+        for dependency in &dependencies_removed {
+            LOGGER.log(&format!("  - {dependency}"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Read the Rust files in a directory and its subdirectories.
+async fn read_rust_sources(directory: &Path) -> Result<Vec<String>> {
+    let mut sources = vec![];
+    if !fs::try_exists(directory).await? {
+        return Ok(sources);
+    }
+
+    let mut directories = vec![directory.to_path_buf()];
+    while let Some(directory) = directories.pop() {
+        let mut entries = fs::read_dir(&directory).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if entry.file_type().await?.is_dir() {
+                directories.push(path);
+            } else if path.extension().is_some_and(|extension| extension == "rs")
+                && path_exists(&path).await?
+            {
+                sources.push(read_file(&path).await?);
+            }
+        }
+    }
+
+    Ok(sources)
+}
+
+/// Whether a crate is referenced by a path, e.g. `tailwind_fuse::tw_merge`.
+fn is_crate_used(name: &str, sources: &[String]) -> bool {
+    let pattern = Regex::new(&format!(
+        r"\b{}\s*::",
+        regex::escape(&name.replace('-', "_"))
+    ))
+    .expect("Pattern should be valid.");
+
+    sources.iter().any(|source| pattern.is_match(source))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use shadcn_registry::schema::FrameworkName;
+
+    use super::*;
+    use crate::utils::{
+        add_components::{AddComponentsOptions, add_components},
+        get_config::Config,
+        test_utils::{TempDir, leptos_project, registry_item},
+    };
+
+    async fn install(dir: &TempDir) -> Config {
+        let config = leptos_project(
+            dir,
+            &[
+                registry_item(
+                    "button",
+                    "ui",
+                    &[("ui/button.rs", "fn a() {}\n")],
+                    &["utils"],
+                ),
+                registry_item("utils", "lib", &[("lib/utils.rs", "pub fn cn() {}\n")], &[]),
+            ],
+        )
+        .await;
+        add_components(
+            &["button".into()],
+            &config,
+            AddComponentsOptions {
+                framework: FrameworkName::Leptos,
+                overwrite: false,
+                silent: true,
+                path: None,
+                aliases: &HashMap::new(),
+            },
+        )
+        .await
+        .unwrap();
+
+        config
+    }
+
+    fn options(dir: &TempDir, component: &str, force: bool) -> RemoveOptions {
+        RemoveOptions {
+            components: vec![component.into()],
+            yes: true,
+            force,
+            cwd: dir.path().to_path_buf(),
+            silent: true,
+            json: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn refuses_removing_dependencies() {
+        let dir = TempDir::new();
+        let config = install(&dir).await;
+
+        assert!(remove(options(&dir, "utils", false)).await.is_err());
+        assert!(dir.path().join("src/lib/utils.rs").exists());
+        assert!(read_lockfile(&config).await.unwrap().get("utils").is_some());
+
+        remove(options(&dir, "utils", true)).await.unwrap();
+        assert!(!dir.path().join("src/lib/utils.rs").exists());
+        assert!(read_lockfile(&config).await.unwrap().get("utils").is_none());
+    }
+
+    #[tokio::test]
+    async fn refuses_removing_modified_files() {
+        let dir = TempDir::new();
+        let config = install(&dir).await;
+        dir.write("src/components/ui/button.rs", "fn a(x: u8) {}\n");
+
+        assert!(remove(options(&dir, "button", false)).await.is_err());
+        assert_eq!(dir.read("src/components/ui/button.rs"), "fn a(x: u8) {}\n");
+        assert!(
+            read_lockfile(&config)
+                .await
+                .unwrap()
+                .get("button")
+                .is_some()
+        );
+
+        remove(options(&dir, "button", true)).await.unwrap();
+        assert!(!dir.path().join("src/components/ui/button.rs").exists());
+        assert!(
+            read_lockfile(&config)
+                .await
+                .unwrap()
+                .get("button")
+                .is_none()
+        );
+        assert!(
+            !read_file(&config.resolved_paths.components.join("ui/mod.rs"))
+                .await
+                .unwrap()
+                .contains("button")
+        );
+    }
+}
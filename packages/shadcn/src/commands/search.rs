@@ -0,0 +1,37 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::commands::list::{get_list_items, print_list_items};
+
+#[derive(Args)]
+pub struct SearchOptions {
+    #[arg(help = "the text to match against item names, descriptions and categories.")]
+    pub query: String,
+
+    #[arg(
+        short,
+        long,
+        help = "the working directory. defaults to the current directory.",
+        default_value = "."
+    )]
+    pub cwd: PathBuf,
+
+    #[arg(long, help = "output as json.")]
+    pub json: bool,
+}
+
+pub async fn search(options: SearchOptions) -> Result<()> {
+    let query = options.query.to_lowercase();
+    let matches = |value: &str| value.to_lowercase().contains(&query);
+
+    let items = get_list_items(&options.cwd, |entry| {
+        matches(&entry.name)
+            || entry.description.as_deref().is_some_and(matches)
+            || entry.category.as_deref().is_some_and(matches)
+    })
+    .await?;
+
+    print_list_items(&items, options.json)
+}
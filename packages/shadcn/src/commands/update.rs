@@ -0,0 +1,730 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Result, bail};
+use clap::Args;
+use shadcn_registry::schema::RegistryEntry;
+use tokio::fs;
+
+use crate::{
+    commands::diff::{get_installed_components, get_transformed_registry_item},
+    utils::{
+        add_components::{AddComponentsOptions, add_components},
+        base_files::{BASE_FILES_DIR, read_base_file, write_base_file},
+        errors::ErrorType,
+        get_config::{CONFIG_FILE_NAME, Config, get_config},
+        get_project_info::get_project_info,
+        highlighter::HIGHLIGHTER,
+        lockfile::{
+            LockedFile, LockedItem, get_installed_file_path, hash_content, lockfile_path,
+            read_lockfile, write_lockfile,
+        },
+        logger::LOGGER,
+        merge::{has_conflict_markers, merge},
+        modules::{add_module_declarations, get_module_layout},
+        plan::{path_exists, read_file, write_file},
+        registry::{get_registry_index, set_registry_config},
+        spinner::{SpinnerOptions, spinner},
+        updaters::update_dependencies::{UpdateDependenciesOptions, update_dependencies},
+    },
+};
+
+#[derive(Args)]
+pub struct UpdateOptions {
+    #[arg(help = "the components to update. defaults to all installed components.")]
+    pub components: Vec<String>,
+
+    #[arg(
+        short,
+        long,
+        help = "the working directory. defaults to the current directory.",
+        default_value = "."
+    )]
+    pub cwd: PathBuf,
+
+    #[arg(short, long, help = "mute output.")]
+    pub silent: bool,
+
+    #[arg(
+        long,
+        requires = "dry_run",
+        help = "output the plan of a dry run as json."
+    )]
+    pub json: bool,
+}
+
+#[derive(Default)]
+struct UpdateSummary {
+    created: Vec<PathBuf>,
+    updated: Vec<PathBuf>,
+    merged: Vec<PathBuf>,
+    conflicted: Vec<(PathBuf, usize)>,
+    unresolved: Vec<PathBuf>,
+    skipped: Vec<PathBuf>,
+}
+
+/// How an installed file was updated to the registry version.
+#[derive(Debug, Eq, PartialEq)]
+enum FileUpdate {
+    Created,
+    Unchanged,
+    Updated,
+    Merged,
+    Conflicted(usize),
+    /// The conflict markers of a previous update are not resolved yet.
+    Unresolved,
+    /// The file has local changes, but the registry version it was installed from is unknown.
+    Skipped,
+}
+
+pub async fn update(options: UpdateOptions) -> Result<()> {
+    if !fs::try_exists(&options.cwd).await? {
+        LOGGER.error(&format!(
+            "The path {} does not exist. Please try again.",
+            HIGHLIGHTER.info(&options.cwd.to_string_lossy())
+        ));
+        bail!("");
+    }
+
+    let config = match get_config(&options.cwd).await {
+        Ok(config) => config,
+        Err(error) => {
+            match error.downcast_ref::<ErrorType>() {
+                Some(ErrorType::MissingConfig) => LOGGER.warn(&format!(
+                    "Configuration is missing. Please run {} to create a {} file.",
+                    HIGHLIGHTER.success("init"),
+                    HIGHLIGHTER.info(CONFIG_FILE_NAME),
+                )),
+                _ => {
+                    LOGGER.error(&error.to_string());
+                    LOGGER.error(&error.root_cause().to_string());
+                }
+            }
+            bail!("");
+        }
+    };
+    set_registry_config(&config);
+
+    let project_info = get_project_info(&options.cwd).await?;
+    let Some(framework) = project_info.framework else {
+        LOGGER.error(&format!(
+            "Could not detect a supported framework at {}.",
+            HIGHLIGHTER.info(&options.cwd.to_string_lossy())
+        ));
+        bail!("");
+    };
+
+    let registry_index = match get_registry_index(framework.name).await {
+        Ok(registry_index) => registry_index,
+        Err(error) => {
+            LOGGER.error("Failed to fetch registry index.");
+            LOGGER.error(&error.to_string());
+            bail!("");
+        }
+    };
+
+    let mut lockfile = read_lockfile(&config).await?;
+
+    // Find the installed components to update.
+    let installed_components =
+        get_installed_components(&registry_index, &lockfile, &config).await?;
+    let installed_names = installed_components
+        .iter()
+        .map(|item| item.name.clone())
+        .collect::<Vec<_>>();
+    let mut components: Vec<&RegistryEntry> = vec![];
+    for name in &options.components {
+        if !registry_index.iter().any(|item| &item.name == name) {
+            LOGGER.error(&format!(
+                "The component {} does not exist.",
+                HIGHLIGHTER.success(name)
+            ));
+            bail!("");
+        }
+        let Some(component) = installed_components.iter().find(|item| &item.name == name) else {
+            LOGGER.error(&format!(
+                "The component {} is not installed. Use {} to install it.",
+                HIGHLIGHTER.success(name),
+                HIGHLIGHTER.success(&format!("add {name}"))
+            ));
+            bail!("");
+        };
+        components.push(component);
+    }
+    if options.components.is_empty() {
+        components = installed_components;
+    }
+
+    if components.is_empty() {
+        LOGGER.info("No installed components found.");
+        return Ok(());
+    }
+
+    let mut registry_spinner = spinner(
+        "Checking registry.",
+        SpinnerOptions {
+            silent: options.silent,
+        },
+    );
+    let aliases = lockfile.aliases();
+    let mut payloads = vec![];
+    for component in components {
+        match get_transformed_registry_item(&component.name, &aliases, &config, framework.name)
+            .await
+        {
+            Ok(payload) => payloads.push(payload),
+            Err(error) => {
+                registry_spinner.fail();
+
+                LOGGER.r#break();
+                LOGGER.error(&format!(
+                    "Failed to fetch {} from registry.",
+                    HIGHLIGHTER.info(&component.name)
+                ));
+                LOGGER.error(&error.to_string());
+                LOGGER.r#break();
+
+                bail!("");
+            }
+        }
+    }
+    registry_spinner.succeed(None);
+
+    update_dependencies(
+        &payloads
+            .iter()
+            .flat_map(|payload| payload.dependencies.iter().flatten().cloned())
+            .collect::<Vec<_>>(),
+        &config,
+        UpdateDependenciesOptions {
+            silent: options.silent,
+        },
+    )
+    .await?;
+
+    let mut files_spinner = spinner(
+        "Updating files.",
+        SpinnerOptions {
+            silent: options.silent,
+        },
+    );
+    let mut summary = UpdateSummary::default();
+    let mut files_created: Vec<PathBuf> = vec![];
+    let mut conflicted_files: Vec<PathBuf> = vec![];
+    // Files which were not updated, so their registry version is unchanged.
+    let mut kept_files: Vec<PathBuf> = vec![];
+
+    for (payload, file) in payloads.iter().flat_map(|payload| {
+        payload
+            .files
+            .iter()
+            .flatten()
+            .map(move |file| (payload, file))
+    }) {
+        let Some(remote) = &file.content else {
+            continue;
+        };
+
+        let locked_item = lockfile.get(&payload.name);
+        let file_path = get_installed_file_path(file, locked_item, &config);
+        let relative_path = file_path
+            .strip_prefix(&config.resolved_paths.cwd)
+            .unwrap_or(&file_path)
+            .to_path_buf();
+        let was_conflicted = locked_item
+            .and_then(|item| item.get_file(&file.path))
+            .is_some_and(|file| file.conflicted);
+
+        match update_file(&file_path, remote, was_conflicted, &config).await? {
+            FileUpdate::Created => {
+                summary.created.push(relative_path);
+                files_created.push(file_path);
+            }
+            FileUpdate::Unchanged => {}
+            FileUpdate::Updated => summary.updated.push(relative_path),
+            FileUpdate::Merged => summary.merged.push(relative_path),
+            FileUpdate::Conflicted(conflicts) => {
+                summary.conflicted.push((relative_path, conflicts));
+                conflicted_files.push(file_path);
+            }
+            FileUpdate::Unresolved => {
+                summary.unresolved.push(relative_path);
+                kept_files.push(file_path);
+            }
+            FileUpdate::Skipped => {
+                summary.skipped.push(relative_path);
+                kept_files.push(file_path);
+            }
+        }
+    }
+
+    // Make new files reachable from the crate root.
+    let layout = get_module_layout(&config).await?;
+    let modules = add_module_declarations(&files_created, &config, layout).await?;
+    summary.created.extend(modules.created);
+    summary.updated.extend(modules.updated);
+
+    // The new registry versions are the base of future updates.
+    for payload in &payloads {
+        let locked_item = lockfile.get(&payload.name).cloned();
+        let mut item = LockedItem::new(
+            payload,
+            framework.name,
+            locked_item.as_ref().and_then(|item| item.alias.clone()),
+            &config,
+            |file| get_installed_file_path(file, locked_item.as_ref(), &config),
+        );
+
+        for file in &mut item.files {
+            let is_path = |path: &PathBuf| lockfile_path(path, &config) == file.path;
+
+            // Files which were not written keep the version they were installed from, or else record their content.
+            if kept_files.iter().any(is_path) {
+                *file = match locked_item
+                    .as_ref()
+                    .and_then(|item| item.get_file(&file.registry_path))
+                {
+                    Some(locked_file) => locked_file.clone(),
+                    None => LockedFile {
+                        hash: hash_content(
+                            &read_file(&config.resolved_paths.cwd.join(&file.path)).await?,
+                        ),
+                        ..file.clone()
+                    },
+                };
+                continue;
+            }
+
+            // Files stay flagged until their conflict markers are resolved.
+            file.conflicted = conflicted_files.iter().any(is_path);
+        }
+        lockfile.insert(item);
+    }
+    write_lockfile(&config, &lockfile).await?;
+
+    if summary.conflicted.is_empty() && summary.unresolved.is_empty() {
+        files_spinner.succeed(None);
+    } else {
+        files_spinner.fail();
+    }
+
+    // Install registry dependencies which were added to the registry since the components were installed.
+    let mut new_dependencies: Vec<String> = vec![];
+    for dependency in payloads
+        .iter()
+        .flat_map(|payload| payload.registry_dependencies.iter().flatten())
+    {
+        if lockfile.get(dependency).is_none()
+            && !installed_names.contains(dependency)
+            && !new_dependencies.contains(dependency)
+        {
+            new_dependencies.push(dependency.clone());
+        }
+    }
+    if !new_dependencies.is_empty() {
+        add_components(
+            &new_dependencies,
+            &config,
+            AddComponentsOptions {
+                framework: framework.name,
+                overwrite: false,
+                silent: options.silent,
+                path: None,
+                aliases: &HashMap::new(),
+            },
+        )
+        .await?;
+    }
+
+    if options.silent {
+        return Ok(());
+    }
+
+    print_summary(&summary);
+
+    Ok(())
+}
+
+/// Update an installed file to the registry version, merging the local changes since the version it was installed
+/// from.
+async fn update_file(
+    file_path: &Path,
+    remote: &str,
+    was_conflicted: bool,
+    config: &Config,
+) -> Result<FileUpdate> {
+    if !path_exists(file_path).await? {
+        write_file(file_path, remote).await?;
+        write_base_file(file_path, remote, config).await?;
+        return Ok(FileUpdate::Created);
+    }
+
+    let local = read_file(file_path).await?;
+    if was_conflicted && has_conflict_markers(&local) {
+        return Ok(FileUpdate::Unresolved);
+    }
+    if local == remote {
+        write_base_file(file_path, remote, config).await?;
+        return Ok(FileUpdate::Unchanged);
+    }
+
+    // Without the registry version the file was installed from, local changes can't be told apart from updates.
+    let Some(base) = read_base_file(file_path, config).await? else {
+        return Ok(FileUpdate::Skipped);
+    };
+    if base == remote {
+        return Ok(FileUpdate::Unchanged);
+    }
+
+    let result = merge(&base, &local, remote);
+    write_file(file_path, result.content).await?;
+    write_base_file(file_path, remote, config).await?;
+
+    Ok(if result.conflicts > 0 {
+        FileUpdate::Conflicted(result.conflicts)
+    } else if base == local {
+        FileUpdate::Updated
+    } else {
+        FileUpdate::Merged
+    })
+}
+
+fn print_summary(summary: &UpdateSummary) {
+    let display = |paths: &Vec<PathBuf>| {
+        paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+    };
+    let conflicted = summary
+        .conflicted
+        .iter()
+        .map(|(path, conflicts)| {
+            format!(
+                "{} ({} {})",
+                path.display(),
+                conflicts,
+                if *conflicts == 1 {
+                    "conflict"
+                } else {
+                    "conflicts"
+                }
+            )
+        })
+        .collect::<Vec<_>>();
+
+    for (label, paths) in [
+        ("Created", display(&summary.created)),
+        ("Updated", display(&summary.updated)),
+        ("Merged local changes into", display(&summary.merged)),
+        ("Conflicts in", conflicted.clone()),
+        ("Unresolved conflicts in", display(&summary.unresolved)),
+        ("Skipped", display(&summary.skipped)),
+    ] {
+        if paths.is_empty() {
+            continue;
+        }
+
+        LOGGER.log(&format!(
+            "{} {} {}:",
+            label,
+            paths.len(),
+            if paths.len() == 1 { "file" } else { "files" }
+        ));
+        for path in paths {
+            LOGGER.log(&format!("  - {path}"));
+        }
+    }
+
+    if summary.created.is_empty()
+        && summary.updated.is_empty()
+        && summary.merged.is_empty()
+        && conflicted.is_empty()
+        && summary.unresolved.is_empty()
+        && summary.skipped.is_empty()
+    {
+        LOGGER.info("Everything is up to date.");
+    }
+
+    if !conflicted.is_empty() || !summary.unresolved.is_empty() {
+        LOGGER.r#break();
+        LOGGER.warn("Resolve the conflict markers, keeping your changes, the registry's or both.");
+    }
+    if !summary.unresolved.is_empty() {
+        LOGGER.warn("Files with unresolved conflicts are not updated until they are resolved.");
+    }
+
+    if !summary.skipped.is_empty() {
+        LOGGER.r#break();
+        LOGGER.warn(&format!(
+            "Skipped files have local changes but no registry version in {} to merge with.",
+            HIGHLIGHTER.info(BASE_FILES_DIR)
+        ));
+        LOGGER.warn(&format!(
+            "Run {} to review the changes or {} to replace them.",
+            HIGHLIGHTER.success("diff <component>"),
+            HIGHLIGHTER.success("add <component> --overwrite")
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shadcn_registry::schema::FrameworkName;
+
+    use super::*;
+    use crate::utils::{
+        base_files::get_base_file_path,
+        lockfile::{LockedFile, Lockfile},
+        test_utils::{TempDir, config, leptos_project, registry_item, write_registry},
+    };
+
+    const BASE: &str = "fn a() {}\n\nfn b() {}\n";
+
+    #[tokio::test]
+    async fn merges_local_changes() {
+        let dir = TempDir::new();
+        let config = config(dir.path()).await;
+        let file_path = dir.write(
+            "src/components/ui/button.rs",
+            "fn a() {}\n\nfn b(x: u8) {}\n",
+        );
+        write_base_file(&file_path, BASE, &config).await.unwrap();
+
+        let remote = "fn a() -> u8 {}\n\nfn b() {}\n";
+        assert_eq!(
+            update_file(&file_path, remote, false, &config)
+                .await
+                .unwrap(),
+            FileUpdate::Merged
+        );
+        assert_eq!(
+            dir.read("src/components/ui/button.rs"),
+            "fn a() -> u8 {}\n\nfn b(x: u8) {}\n"
+        );
+        assert_eq!(
+            read_base_file(&file_path, &config).await.unwrap().unwrap(),
+            remote
+        );
+
+        assert_eq!(
+            update_file(&file_path, remote, false, &config)
+                .await
+                .unwrap(),
+            FileUpdate::Unchanged
+        );
+    }
+
+    #[tokio::test]
+    async fn keeps_conflicts_until_resolved() {
+        let dir = TempDir::new();
+        let config = config(dir.path()).await;
+        let file_path = dir.write(
+            "src/components/ui/button.rs",
+            "fn a() {}\n\nfn b(x: u8) {}\n",
+        );
+        write_base_file(&file_path, BASE, &config).await.unwrap();
+
+        assert_eq!(
+            update_file(&file_path, "fn a() {}\n\nfn b() -> u8 {}\n", false, &config)
+                .await
+                .unwrap(),
+            FileUpdate::Conflicted(1)
+        );
+        let conflicted = dir.read("src/components/ui/button.rs");
+        assert!(has_conflict_markers(&conflicted));
+
+        // A later registry version does not touch the conflict markers.
+        assert_eq!(
+            update_file(&file_path, "fn a() {}\n\nfn b() -> u16 {}\n", true, &config)
+                .await
+                .unwrap(),
+            FileUpdate::Unresolved
+        );
+        assert_eq!(dir.read("src/components/ui/button.rs"), conflicted);
+
+        // Once resolved, the resolution is merged like other local changes.
+        dir.write(
+            "src/components/ui/button.rs",
+            "fn a() {}\n\nfn b(x: u8) -> u8 {}\n",
+        );
+        assert_eq!(
+            update_file(
+                &file_path,
+                "fn a() -> u8 {}\n\nfn b() -> u8 {}\n",
+                true,
+                &config
+            )
+            .await
+            .unwrap(),
+            FileUpdate::Merged
+        );
+        assert_eq!(
+            dir.read("src/components/ui/button.rs"),
+            "fn a() -> u8 {}\n\nfn b(x: u8) -> u8 {}\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn skips_local_changes_without_base() {
+        let dir = TempDir::new();
+        let config = config(dir.path()).await;
+        let file_path = dir.write("src/components/ui/button.rs", "fn a(x: u8) {}\n");
+
+        assert_eq!(
+            update_file(&file_path, "fn a() -> u8 {}\n", false, &config)
+                .await
+                .unwrap(),
+            FileUpdate::Skipped
+        );
+        assert_eq!(dir.read("src/components/ui/button.rs"), "fn a(x: u8) {}\n");
+
+        let new_path = dir.path().join("src/components/ui/badge.rs");
+        assert_eq!(
+            update_file(&new_path, "fn badge() {}\n", false, &config)
+                .await
+                .unwrap(),
+            FileUpdate::Created
+        );
+    }
+
+    async fn install(dir: &TempDir, items: &[serde_json::Value], names: &[&str]) -> Config {
+        let config = leptos_project(dir, items).await;
+        add_components(
+            &names
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>(),
+            &config,
+            AddComponentsOptions {
+                framework: FrameworkName::Leptos,
+                overwrite: false,
+                silent: true,
+                path: None,
+                aliases: &HashMap::new(),
+            },
+        )
+        .await
+        .unwrap();
+
+        config
+    }
+
+    async fn update_all(dir: &TempDir) {
+        update(UpdateOptions {
+            components: vec![],
+            cwd: dir.path().to_path_buf(),
+            silent: true,
+            json: false,
+        })
+        .await
+        .unwrap();
+    }
+
+    fn locked_file<'a>(lockfile: &'a Lockfile, name: &str) -> &'a LockedFile {
+        &lockfile.get(name).unwrap().files[0]
+    }
+
+    #[tokio::test]
+    async fn keeps_locked_version_of_files_not_updated() {
+        let dir = TempDir::new();
+        let button = |content| registry_item("button", "ui", &[("ui/button.rs", content)], &[]);
+        let card = |content| registry_item("card", "ui", &[("ui/card.rs", content)], &[]);
+        let config = install(
+            &dir,
+            &[button("fn a() {}\n"), card("fn a() {}\n")],
+            &["button", "card"],
+        )
+        .await;
+
+        // Local changes without a base version are skipped, local changes to the same line conflict.
+        let button_path = dir.write("src/components/ui/button.rs", "fn a(x: u8) {}\n");
+        fs::remove_file(get_base_file_path(&button_path, &config))
+            .await
+            .unwrap();
+        dir.write("src/components/ui/card.rs", "fn a(x: u8) {}\n");
+        write_registry(
+            &dir,
+            &[button("fn a() -> u8 {}\n"), card("fn a() -> u8 {}\n")],
+        );
+        update_all(&dir).await;
+
+        let lockfile = read_lockfile(&config).await.unwrap();
+        assert_eq!(dir.read("src/components/ui/button.rs"), "fn a(x: u8) {}\n");
+        assert_eq!(
+            locked_file(&lockfile, "button").hash,
+            hash_content("fn a() {}\n")
+        );
+        assert!(has_conflict_markers(&dir.read("src/components/ui/card.rs")));
+        assert_eq!(
+            locked_file(&lockfile, "card").hash,
+            hash_content("fn a() -> u8 {}\n")
+        );
+        assert!(locked_file(&lockfile, "card").conflicted);
+
+        // Unresolved conflicts keep the version they were merged with.
+        write_registry(
+            &dir,
+            &[button("fn a() -> u16 {}\n"), card("fn a() -> u16 {}\n")],
+        );
+        update_all(&dir).await;
+
+        let lockfile = read_lockfile(&config).await.unwrap();
+        assert_eq!(
+            locked_file(&lockfile, "button").hash,
+            hash_content("fn a() {}\n")
+        );
+        assert_eq!(
+            locked_file(&lockfile, "card").hash,
+            hash_content("fn a() -> u8 {}\n")
+        );
+        assert!(locked_file(&lockfile, "card").conflicted);
+        assert_eq!(
+            read_base_file(&dir.path().join("src/components/ui/card.rs"), &config)
+                .await
+                .unwrap()
+                .unwrap(),
+            "fn a() -> u8 {}\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn installs_new_registry_dependencies() {
+        let dir = TempDir::new();
+        let config = install(
+            &dir,
+            &[registry_item(
+                "button",
+                "ui",
+                &[("ui/button.rs", "fn a() {}\n")],
+                &[],
+            )],
+            &["button"],
+        )
+        .await;
+
+        write_registry(
+            &dir,
+            &[
+                registry_item(
+                    "button",
+                    "ui",
+                    &[("ui/button.rs", "fn a() {}\n")],
+                    &["utils"],
+                ),
+                registry_item("utils", "lib", &[("lib/utils.rs", "pub fn cn() {}\n")], &[]),
+            ],
+        );
+        update_all(&dir).await;
+
+        assert_eq!(dir.read("src/lib/utils.rs"), "pub fn cn() {}\n");
+        let lockfile = read_lockfile(&config).await.unwrap();
+        assert!(lockfile.get("utils").is_some());
+        assert_eq!(
+            lockfile.get("button").unwrap().registry_dependencies,
+            ["utils"]
+        );
+    }
+}
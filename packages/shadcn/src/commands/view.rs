@@ -0,0 +1,234 @@
+use std::{
+    collections::HashMap,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+    slice,
+};
+
+use anyhow::{Result, bail};
+use clap::Args;
+use serde::Serialize;
+use shadcn_registry::schema::{RegistryEntry, RegistryItemDependency};
+use tokio::fs;
+
+use crate::{
+    commands::diff::get_transformed_registry_item,
+    utils::{
+        errors::ErrorType,
+        get_config::{DEFAULT_STYLE, get_config},
+        get_project_info::get_project_info,
+        highlighter::{HIGHLIGHTER, highlight_code},
+        lockfile::read_lockfile,
+        logger::LOGGER,
+        registry::{registry_resolve_items, set_registry_config},
+    },
+};
+
+#[derive(Args)]
+pub struct ViewOptions {
+    #[arg(required = true, help = "the items to view.")]
+    pub items: Vec<String>,
+
+    #[arg(
+        short,
+        long,
+        help = "the working directory. defaults to the current directory.",
+        default_value = "."
+    )]
+    pub cwd: PathBuf,
+
+    #[arg(long, help = "output as json.")]
+    pub json: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ViewItem {
+    #[serde(flatten)]
+    entry: RegistryEntry,
+    /// Direct and transitive registry dependencies, in the order they are resolved.
+    resolved_registry_dependencies: Vec<String>,
+}
+
+pub async fn view(options: ViewOptions) -> Result<()> {
+    if !fs::try_exists(&options.cwd).await? {
+        LOGGER.error(&format!(
+            "The path {} does not exist. Please try again.",
+            HIGHLIGHTER.info(&options.cwd.to_string_lossy())
+        ));
+        bail!("");
+    }
+
+    let project_info = get_project_info(&options.cwd).await?;
+    let Some(framework) = project_info.framework else {
+        LOGGER.error(&format!(
+            "Could not detect a supported framework at {}.",
+            HIGHLIGHTER.info(&options.cwd.to_string_lossy())
+        ));
+        bail!("");
+    };
+
+    // Without components.toml, items are shown in the default style and untransformed.
+    let config = match get_config(&options.cwd).await {
+        Ok(config) => {
+            set_registry_config(&config);
+            Some(config)
+        }
+        Err(error) => match error.downcast_ref::<ErrorType>() {
+            Some(ErrorType::MissingConfig) => None,
+            _ => {
+                LOGGER.error(&error.to_string());
+                LOGGER.error(&error.root_cause().to_string());
+                bail!("");
+            }
+        },
+    };
+
+    let style = config.as_ref().map_or(DEFAULT_STYLE, |config| config.style);
+    let aliases = match &config {
+        Some(config) => read_lockfile(config).await?.aliases(),
+        None => HashMap::new(),
+    };
+
+    let mut items = vec![];
+    for name in &options.items {
+        let mut resolved =
+            match registry_resolve_items(slice::from_ref(name), framework.name, style).await {
+                Ok(resolved) => resolved,
+                Err(error) => {
+                    LOGGER.r#break();
+                    LOGGER.error(&format!(
+                        "Failed to fetch {} from registry.",
+                        HIGHLIGHTER.info(name)
+                    ));
+                    LOGGER.error(&error.to_string());
+                    LOGGER.r#break();
+
+                    bail!("");
+                }
+            };
+
+        // Dependencies are resolved before the item itself.
+        let mut entry = resolved
+            .pop()
+            .expect("Resolved items should include the item.");
+
+        // Show the files as they would be installed.
+        if let Some(config) = &config {
+            entry =
+                match get_transformed_registry_item(&entry.name, &aliases, config, framework.name)
+                    .await
+                {
+                    Ok(entry) => entry,
+                    Err(error) => {
+                        LOGGER.r#break();
+                        LOGGER.error(&format!(
+                            "Failed to fetch {} from registry.",
+                            HIGHLIGHTER.info(name)
+                        ));
+                        LOGGER.error(&error.to_string());
+                        LOGGER.r#break();
+
+                        bail!("");
+                    }
+                };
+        }
+
+        items.push(ViewItem {
+            entry,
+            resolved_registry_dependencies: resolved.into_iter().map(|item| item.name).collect(),
+        });
+    }
+
+    if options.json {
+        LOGGER.log(&serde_json::to_string_pretty(&items)?);
+        return Ok(());
+    }
+
+    for item in &items {
+        print_item(item);
+    }
+
+    Ok(())
+}
+
+fn print_item(item: &ViewItem) {
+    let entry = &item.entry;
+    let highlight = io::stdout().is_terminal();
+
+    LOGGER.log(&format!(
+        "{} {}",
+        HIGHLIGHTER.info(&entry.name),
+        entry.r#type
+    ));
+    if let Some(description) = &entry.description {
+        LOGGER.log(description);
+    }
+    LOGGER.r#break();
+
+    let to_strings = |values: &Option<Vec<RegistryItemDependency>>| {
+        values
+            .iter()
+            .flatten()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    };
+    for (label, values) in [
+        ("Dependencies", to_strings(&entry.dependencies)),
+        ("Dev dependencies", to_strings(&entry.dev_dependencies)),
+        (
+            "Registry dependencies",
+            entry.registry_dependencies.clone().unwrap_or_default(),
+        ),
+        (
+            "Resolved registry dependencies",
+            item.resolved_registry_dependencies.clone(),
+        ),
+    ] {
+        if values.is_empty() {
+            continue;
+        }
+
+        LOGGER.log(&format!("{label}:"));
+        for value in values {
+            LOGGER.log(&format!("  - {value}"));
+        }
+    }
+
+    if let Some(css_vars) = entry.css_vars.as_ref().filter(|vars| !vars.is_empty()) {
+        LOGGER.log("CSS variables:");
+        let mut modes = css_vars.iter().collect::<Vec<_>>();
+        modes.sort_by_key(|(mode, _)| mode.to_string());
+        for (mode, vars) in modes {
+            LOGGER.log(&format!("  {mode}:"));
+            let mut vars = vars.iter().collect::<Vec<_>>();
+            vars.sort();
+            for (name, value) in vars {
+                LOGGER.log(&format!("    --{name}: {value};"));
+            }
+        }
+    }
+
+    for file in entry.files.iter().flatten() {
+        LOGGER.r#break();
+        LOGGER.log(&HIGHLIGHTER.info(&format!(
+            "{} ({})",
+            file.target.as_deref().unwrap_or(&file.path),
+            file.r#type
+        )));
+
+        match &file.content {
+            Some(content) if highlight => {
+                let extension = Path::new(&file.path)
+                    .extension()
+                    .map(|extension| extension.to_string_lossy())
+                    .unwrap_or_default();
+                LOGGER.log(&highlight_code(content, &extension));
+            }
+            Some(content) => LOGGER.log(content),
+            None => LOGGER.log("(no content)"),
+        }
+    }
+
+    LOGGER.r#break();
+}
@@ -4,3 +4,5 @@
 pub mod commands;
 mod preflights;
 mod utils;
+
+pub use utils::plan;
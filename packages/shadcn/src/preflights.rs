@@ -1 +1,2 @@
+pub mod preflight_add;
 pub mod preflight_init;
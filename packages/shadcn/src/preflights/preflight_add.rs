@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use anyhow::{Result, bail};
+use tokio::fs;
+
+use crate::{
+    commands::add::AddOptions,
+    utils::{
+        errors::ErrorType,
+        get_config::{CONFIG_FILE_NAME, Config, get_config},
+        highlighter::HIGHLIGHTER,
+        logger::LOGGER,
+    },
+};
+
+pub struct PreFlightAddResult {
+    pub errors: HashMap<ErrorType, bool>,
+    pub config: Option<Config>,
+}
+
+pub async fn pre_flight_add(options: &AddOptions) -> Result<PreFlightAddResult> {
+    let mut errors: HashMap<ErrorType, bool> = HashMap::new();
+
+    // Ensure target directory exists.
+    // Check for empty project. We assume if no Cargo.toml exists, the project is empty.
+    if !fs::try_exists(&options.cwd).await?
+        || !fs::try_exists(options.cwd.join("Cargo.toml")).await?
+    {
+        errors.insert(ErrorType::MissingDirOrEmptyProject, true);
+
+        return Ok(PreFlightAddResult {
+            errors,
+            config: None,
+        });
+    }
+
+    match get_config(&options.cwd).await {
+        Ok(config) => Ok(PreFlightAddResult {
+            errors,
+            config: Some(config),
+        }),
+        Err(error) => match error.downcast_ref::<ErrorType>() {
+            Some(ErrorType::MissingConfig) => {
+                errors.insert(ErrorType::MissingConfig, true);
+
+                Ok(PreFlightAddResult {
+                    errors,
+                    config: None,
+                })
+            }
+            _ => {
+                LOGGER.r#break();
+                LOGGER.error(&format!(
+                    "An invalid {} file was found at {}.\nBefore you can add components, you must create a valid {} file by running the {} command.",
+                    HIGHLIGHTER.info(CONFIG_FILE_NAME),
+                    HIGHLIGHTER.info(&options.cwd.to_string_lossy()),
+                    HIGHLIGHTER.info(CONFIG_FILE_NAME),
+                    HIGHLIGHTER.info("init"),
+                ));
+                LOGGER.error(&error.root_cause().to_string());
+                LOGGER.r#break();
+
+                bail!("");
+            }
+        },
+    }
+}
@@ -1,13 +1,15 @@
 use std::collections::HashMap;
 
 use anyhow::{Result, bail};
+use shadcn_registry::registry_frameworks::FRAMEWORKS;
 use tokio::fs;
 
 use crate::{
     commands::init::InitOptions,
     utils::{
         errors::ErrorType,
-        get_project_info::{ProjectInfo, get_project_info},
+        get_config::CONFIG_FILE_NAME,
+        get_project_info::{ProjectInfo, TailwindVersion, get_project_info},
         highlighter::HIGHLIGHTER,
         logger::LOGGER,
         spinner::{SpinnerOptions, spinner},
@@ -19,7 +21,7 @@ pub struct PreFlightInitResult {
     pub project_info: Option<ProjectInfo>,
 }
 
-pub async fn pre_flight_init(options: InitOptions) -> Result<PreFlightInitResult> {
+pub async fn pre_flight_init(options: &InitOptions) -> Result<PreFlightInitResult> {
     let mut errors: HashMap<ErrorType, bool> = HashMap::new();
 
     // Ensure target directory exists.
@@ -42,15 +44,15 @@ pub async fn pre_flight_init(options: InitOptions) -> Result<PreFlightInitResult
         },
     );
 
-    if fs::try_exists(options.cwd.join("components.toml")).await? && !options.force {
+    if fs::try_exists(options.cwd.join(CONFIG_FILE_NAME)).await? && !options.force {
         project_spinner.fail();
 
         LOGGER.r#break();
         LOGGER.error(&format!(
             "A {} file already exists at {}.\nTo start over, remove the {} file and run {} again.",
-            HIGHLIGHTER.info("components.toml"),
+            HIGHLIGHTER.info(CONFIG_FILE_NAME),
             HIGHLIGHTER.info(&options.cwd.to_string_lossy()),
-            HIGHLIGHTER.info("components.toml"),
+            HIGHLIGHTER.info(CONFIG_FILE_NAME),
             HIGHLIGHTER.info("init"),
         ));
         LOGGER.r#break();
@@ -60,14 +62,43 @@ pub async fn pre_flight_init(options: InitOptions) -> Result<PreFlightInitResult
 
     project_spinner.succeed(None);
 
-    // let framework_spinner = spinner("Verifying framework.", SpinnerOptions { silent: true });
+    let mut framework_spinner = spinner(
+        "Verifying framework.",
+        SpinnerOptions {
+            silent: options.silent,
+        },
+    );
     let project_info = get_project_info(&options.cwd).await?;
-    // TODO
-    // if project_info.framework.name == "manual" {}
-    // framework_spinner.succeed(Some(format!("Verifying framework. Found {}.", HIGHLIGHTER.info(project_info.framework.label))));
+    let Some(framework) = &project_info.framework else {
+        errors.insert(ErrorType::UnsupportedFramework, true);
+        framework_spinner.fail();
+
+        LOGGER.r#break();
+        LOGGER.error(&format!(
+            "We could not detect a supported framework at {}.\nAdd {} to your {} and try again.",
+            HIGHLIGHTER.info(&options.cwd.to_string_lossy()),
+            FRAMEWORKS
+                .iter()
+                .map(|framework| HIGHLIGHTER.info(&framework.detect_dependencies.join(", ")))
+                .collect::<Vec<_>>()
+                .join(" or "),
+            HIGHLIGHTER.info("Cargo.toml"),
+        ));
+        LOGGER.r#break();
+
+        bail!("");
+    };
+    framework_spinner.succeed(Some(format!(
+        "Verifying framework. Found {}.",
+        HIGHLIGHTER.info(&framework.label)
+    )));
 
     let mut tailwind_spinner = spinner("Validating Tailwind CSS.", SpinnerOptions { silent: true });
-    if project_info.tailwind_config_file.is_none() || project_info.tailwind_css_file.is_none() {
+    // Tailwind CSS v4 is configured in CSS and does not need a config file.
+    let tailwind_config_required = project_info.tailwind_version == TailwindVersion::V3;
+    if (tailwind_config_required && project_info.tailwind_config_file.is_none())
+        || project_info.tailwind_css_file.is_none()
+    {
         errors.insert(ErrorType::TailwindNotConfigured, true);
         tailwind_spinner.fail();
     } else {
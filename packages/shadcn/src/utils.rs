@@ -1,5 +1,19 @@
+pub mod add_components;
+pub mod base_files;
+pub mod create_project;
 pub mod errors;
+pub mod get_config;
 pub mod get_project_info;
 pub mod highlighter;
+pub mod lockfile;
 pub mod logger;
+pub mod merge;
+pub mod modules;
+pub mod plan;
+pub mod prompts;
+pub mod registry;
 pub mod spinner;
+#[cfg(test)]
+pub mod test_utils;
+pub mod transformers;
+pub mod updaters;
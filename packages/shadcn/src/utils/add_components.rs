@@ -0,0 +1,279 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Result, bail};
+use shadcn_registry::schema::{FrameworkName, RegistryEntry};
+
+use crate::utils::{
+    get_config::Config,
+    highlighter::HIGHLIGHTER,
+    lockfile::{LockedItem, Lockfile, hash_content, read_lockfile, write_lockfile},
+    logger::LOGGER,
+    plan::{path_exists, read_file},
+    registry::{
+        get_registry_base_color, get_registry_item_file_path, registry_items_tree,
+        registry_resolve_items,
+    },
+    spinner::{SpinnerOptions, spinner},
+    transformers::{TransformOptions, transform_alias::get_alias_renames, transform_items},
+    updaters::{
+        update_dependencies::{UpdateDependenciesOptions, update_dependencies},
+        update_files::{UpdateFilesOptions, update_files},
+    },
+};
+
+pub struct AddComponentsOptions<'a> {
+    pub framework: FrameworkName,
+    pub overwrite: bool,
+    pub silent: bool,
+    pub path: Option<&'a Path>,
+    /// Aliases for the public identifiers of items, e.g. `button` to `FancyButton`.
+    pub aliases: &'a HashMap<String, String>,
+}
+
+pub async fn add_components(
+    components: &[String],
+    config: &Config,
+    options: AddComponentsOptions<'_>,
+) -> Result<()> {
+    let mut registry_spinner = spinner(
+        "Checking registry.",
+        SpinnerOptions {
+            silent: options.silent,
+        },
+    );
+    let mut items = match registry_resolve_items(components, options.framework, config.style).await
+    {
+        Ok(items) => items,
+        Err(error) => {
+            registry_spinner.fail();
+
+            LOGGER.r#break();
+            LOGGER.error("Failed to fetch components from registry.");
+            LOGGER.error(&error.to_string());
+            LOGGER.r#break();
+
+            bail!("");
+        }
+    };
+    // Colors are inlined into components when CSS variables are disabled.
+    let base_color = if config.tailwind.css_variables {
+        None
+    } else {
+        match get_registry_base_color(&config.tailwind.base_color).await {
+            Ok(base_color) => Some(base_color),
+            Err(error) => {
+                registry_spinner.fail();
+
+                LOGGER.r#break();
+                LOGGER.error("Failed to fetch base color from registry.");
+                LOGGER.error(&error.to_string());
+                LOGGER.r#break();
+
+                bail!("");
+            }
+        }
+    };
+    registry_spinner.succeed(None);
+
+    let mut lockfile = read_lockfile(config).await?;
+    let aliases = get_installed_aliases(&items, &lockfile, config, &options).await?;
+    let renames = get_alias_renames(&items, &aliases);
+    transform_items(
+        &mut items,
+        &TransformOptions {
+            config,
+            framework: options.framework,
+            base_color: base_color.as_ref(),
+            renames: &renames,
+        },
+    );
+    let tree = registry_items_tree(items.clone());
+
+    // Existing files are kept unless they are overwritten.
+    let mut kept_files = vec![];
+    if !options.overwrite {
+        for file in &tree.files {
+            let file_path = get_registry_item_file_path(file, config, options.path);
+            if path_exists(&file_path).await? {
+                kept_files.push(file_path);
+            }
+        }
+    }
+
+    update_dependencies(
+        &tree.dependencies,
+        config,
+        UpdateDependenciesOptions {
+            silent: options.silent,
+        },
+    )
+    .await?;
+
+    update_files(
+        &tree.files,
+        config,
+        UpdateFilesOptions {
+            overwrite: options.overwrite,
+            silent: options.silent,
+            path: options.path,
+        },
+    )
+    .await?;
+
+    update_lockfile(
+        &mut lockfile,
+        &items,
+        &aliases,
+        &kept_files,
+        config,
+        &options,
+    )
+    .await?;
+
+    if let Some(docs) = tree.docs {
+        LOGGER.info(&docs);
+    }
+
+    Ok(())
+}
+
+/// Get the aliases the items are installed with.
+///
+/// Items whose files are kept retain the alias they were installed with, so their dependents refer to the existing
+/// identifiers. Other items use the given alias, or else the alias they were installed with.
+async fn get_installed_aliases(
+    items: &[RegistryEntry],
+    lockfile: &Lockfile,
+    config: &Config,
+    options: &AddComponentsOptions<'_>,
+) -> Result<HashMap<String, String>> {
+    let mut aliases = HashMap::new();
+
+    for item in items {
+        let locked_alias = lockfile.get(&item.name).and_then(|item| item.alias.clone());
+
+        let mut is_kept = false;
+        if !options.overwrite {
+            for file in item.files.iter().flatten() {
+                if path_exists(&get_registry_item_file_path(file, config, options.path)).await? {
+                    is_kept = true;
+                    break;
+                }
+            }
+        }
+
+        let alias = match options.aliases.get(&item.name) {
+            Some(alias) if is_kept => {
+                if !options.silent && locked_alias.as_ref() != Some(alias) {
+                    LOGGER.warn(&format!(
+                        "The component {} is already installed, so it is not renamed to {}. Use {} to reinstall it.",
+                        HIGHLIGHTER.success(&item.name),
+                        HIGHLIGHTER.success(alias),
+                        HIGHLIGHTER.info("--overwrite")
+                    ));
+                }
+                locked_alias
+            }
+            Some(alias) => Some(alias.clone()),
+            None => locked_alias,
+        };
+        if let Some(alias) = alias {
+            aliases.insert(item.name.clone(), alias);
+        }
+    }
+
+    Ok(aliases)
+}
+
+/// Record the installed items and the hashes of their files in the lockfile.
+///
+/// `kept_files` are files which existed and were not written, so their content is recorded instead.
+async fn update_lockfile(
+    lockfile: &mut Lockfile,
+    items: &[RegistryEntry],
+    aliases: &HashMap<String, String>,
+    kept_files: &[PathBuf],
+    config: &Config,
+    options: &AddComponentsOptions<'_>,
+) -> Result<()> {
+    for item in items {
+        // Existing files are kept, so the recorded versions still apply.
+        if !options.overwrite && lockfile.get(&item.name).is_some() {
+            continue;
+        }
+
+        let mut locked_item = LockedItem::new(
+            item,
+            options.framework,
+            aliases.get(&item.name).cloned(),
+            config,
+            |file| get_registry_item_file_path(file, config, options.path),
+        );
+        for file in &mut locked_item.files {
+            let file_path = config.resolved_paths.cwd.join(&file.path);
+            if kept_files.contains(&file_path) {
+                file.hash = hash_content(&read_file(&file_path).await?);
+            }
+        }
+        lockfile.insert(locked_item);
+    }
+
+    write_lockfile(config, lockfile).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{
+        lockfile::verify_lockfile,
+        test_utils::{TempDir, leptos_project, registry_item},
+    };
+
+    #[tokio::test]
+    async fn locks_content_of_kept_files() {
+        let dir = TempDir::new();
+        let config = leptos_project(
+            &dir,
+            &[
+                registry_item("button", "ui", &[("ui/button.rs", "fn a() {}\n")], &[]),
+                registry_item("card", "ui", &[("ui/card.rs", "fn b() {}\n")], &[]),
+            ],
+        )
+        .await;
+        dir.write("src/components/ui/button.rs", "fn a(x: u8) {}\n");
+
+        add_components(
+            &["button".into(), "card".into()],
+            &config,
+            AddComponentsOptions {
+                framework: FrameworkName::Leptos,
+                overwrite: false,
+                silent: true,
+                path: None,
+                aliases: &HashMap::new(),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(dir.read("src/components/ui/button.rs"), "fn a(x: u8) {}\n");
+        let lockfile = read_lockfile(&config).await.unwrap();
+        assert_eq!(
+            lockfile.get("button").unwrap().files[0].hash,
+            hash_content("fn a(x: u8) {}\n")
+        );
+        assert_eq!(
+            lockfile.get("card").unwrap().files[0].hash,
+            hash_content("fn b() {}\n")
+        );
+        assert!(
+            verify_lockfile(&lockfile, &config)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
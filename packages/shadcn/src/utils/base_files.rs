@@ -0,0 +1,33 @@
+use std::path::{Path, PathBuf};
+
+use crate::utils::{
+    get_config::Config,
+    plan::{path_exists, read_file, write_file},
+};
+use anyhow::Result;
+
+/// Directory, relative to the project root, with the registry version of each installed file.
+pub const BASE_FILES_DIR: &str = ".shadcn/base";
+
+/// Get the path of the registry version of an installed file.
+pub fn get_base_file_path(file_path: &Path, config: &Config) -> PathBuf {
+    let cwd = &config.resolved_paths.cwd;
+
+    cwd.join(BASE_FILES_DIR)
+        .join(file_path.strip_prefix(cwd).unwrap_or(file_path))
+}
+
+/// Read the registry version of an installed file, if it was recorded.
+pub async fn read_base_file(file_path: &Path, config: &Config) -> Result<Option<String>> {
+    let base_path = get_base_file_path(file_path, config);
+    if !path_exists(&base_path).await? {
+        return Ok(None);
+    }
+
+    Ok(Some(read_file(&base_path).await?))
+}
+
+/// Record the registry version of an installed file, to merge local changes on update.
+pub async fn write_base_file(file_path: &Path, content: &str, config: &Config) -> Result<()> {
+    write_file(&get_base_file_path(file_path, config), content).await
+}
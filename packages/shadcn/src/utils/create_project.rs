@@ -0,0 +1,195 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, bail};
+use shadcn_registry::{registry_frameworks::FRAMEWORKS, schema::FrameworkName};
+use tokio::fs;
+
+use crate::utils::{
+    get_config::DEFAULT_TAILWIND_CSS,
+    highlighter::HIGHLIGHTER,
+    logger::LOGGER,
+    plan::write_file,
+    prompts::{is_interactive, prompt_confirm, prompt_select},
+    spinner::{SpinnerOptions, spinner},
+};
+
+const DEFAULT_FRAMEWORK: FrameworkName = FrameworkName::Leptos;
+const DEFAULT_PROJECT_NAME: &str = "my-app";
+const TAILWIND_CSS: &str = "style/tailwind.css";
+
+const TRUNK_TOML: &str = include_str!("../../templates/common/Trunk.toml");
+const INDEX_HTML: &str = include_str!("../../templates/common/index.html");
+const TAILWIND_CONFIG_JS: &str = include_str!("../../templates/common/tailwind.config.js");
+const TAILWIND_CSS_FILE: &str = include_str!("../../templates/common/tailwind.css");
+
+pub struct CreateProjectOptions {
+    pub framework: Option<FrameworkName>,
+    pub force: bool,
+    pub silent: bool,
+    pub src_dir: bool,
+    pub yes: bool,
+}
+
+/// Scaffold a minimal Trunk app with Tailwind CSS in `cwd`.
+pub async fn create_project(cwd: &Path, options: CreateProjectOptions) -> Result<()> {
+    if is_interactive()
+        && !options.yes
+        && !prompt_confirm(
+            &format!(
+                "The path {} does not contain a {}. Would you like to start a new project?",
+                HIGHLIGHTER.info(&cwd.to_string_lossy()),
+                HIGHLIGHTER.info("Cargo.toml"),
+            ),
+            true,
+        )?
+    {
+        bail!("");
+    }
+
+    let framework = match options.framework {
+        Some(framework) => framework,
+        None if is_interactive() => {
+            FRAMEWORKS[prompt_select(
+                "Which framework would you like to use?",
+                &FRAMEWORKS
+                    .iter()
+                    .map(|framework| framework.label.as_str())
+                    .collect::<Vec<_>>(),
+                FRAMEWORKS
+                    .iter()
+                    .position(|framework| framework.name == DEFAULT_FRAMEWORK)
+                    .unwrap_or_default(),
+            )?]
+            .name
+        }
+        None => DEFAULT_FRAMEWORK,
+    };
+    let Some(framework_label) = FRAMEWORKS
+        .iter()
+        .find(|entry| entry.name == framework)
+        .map(|entry| entry.label.clone())
+    else {
+        bail!("Unknown framework `{framework}`.");
+    };
+
+    let name = project_name(cwd);
+    let tailwind_css = if options.src_dir {
+        DEFAULT_TAILWIND_CSS
+    } else {
+        TAILWIND_CSS
+    };
+
+    let (cargo_toml, main_rs) = match framework {
+        FrameworkName::Dioxus => (
+            include_str!("../../templates/dioxus/Cargo.toml.template"),
+            include_str!("../../templates/dioxus/main.rs.template"),
+        ),
+        FrameworkName::Leptos => (
+            include_str!("../../templates/leptos/Cargo.toml.template"),
+            include_str!("../../templates/leptos/main.rs.template"),
+        ),
+        FrameworkName::Yew => (
+            include_str!("../../templates/yew/Cargo.toml.template"),
+            include_str!("../../templates/yew/main.rs.template"),
+        ),
+    };
+
+    let files: Vec<(PathBuf, &str)> = vec![
+        ("Cargo.toml".into(), cargo_toml),
+        ("Trunk.toml".into(), TRUNK_TOML),
+        ("index.html".into(), INDEX_HTML),
+        ("tailwind.config.js".into(), TAILWIND_CONFIG_JS),
+        (tailwind_css.into(), TAILWIND_CSS_FILE),
+        ("src/main.rs".into(), main_rs),
+    ];
+
+    if !options.force {
+        let mut existing_files = vec![];
+        for (path, _) in &files {
+            if fs::try_exists(cwd.join(path)).await? {
+                existing_files.push(path.to_string_lossy().to_string());
+            }
+        }
+
+        if !existing_files.is_empty() {
+            LOGGER.r#break();
+            LOGGER.error(&format!(
+                "The directory {} already contains {}.\nTo overwrite these files, run {} again with {}.",
+                HIGHLIGHTER.info(&cwd.to_string_lossy()),
+                HIGHLIGHTER.info(&existing_files.join(", ")),
+                HIGHLIGHTER.info("init"),
+                HIGHLIGHTER.info("--force"),
+            ));
+            LOGGER.r#break();
+
+            bail!("");
+        }
+    }
+
+    let mut create_spinner = spinner(
+        format!(
+            "Creating a new {} project in {}.",
+            HIGHLIGHTER.info(&framework_label),
+            HIGHLIGHTER.info(&cwd.to_string_lossy())
+        ),
+        SpinnerOptions {
+            silent: options.silent,
+        },
+    );
+
+    for (path, template) in files {
+        let content = template
+            .replace("{{ name }}", &name)
+            .replace("{{ tailwind_css }}", tailwind_css);
+        write_file(&cwd.join(path), content).await?;
+    }
+
+    create_spinner.succeed(None);
+
+    Ok(())
+}
+
+/// Parse a framework name from the command line.
+pub fn parse_framework_name(value: &str) -> Result<FrameworkName, String> {
+    FRAMEWORKS
+        .iter()
+        .find(|framework| framework.name.to_string() == value)
+        .map(|framework| framework.name)
+        .ok_or_else(|| {
+            format!(
+                "possible values: {}",
+                FRAMEWORKS
+                    .iter()
+                    .map(|framework| framework.name.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+}
+
+/// Derive a valid package name from the directory name.
+fn project_name(cwd: &Path) -> String {
+    let name = std::path::absolute(cwd)
+        .ok()
+        .and_then(|path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().to_lowercase())
+        })
+        .unwrap_or_default()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>();
+    let name = name.trim_matches('-');
+
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        DEFAULT_PROJECT_NAME.into()
+    } else {
+        name.into()
+    }
+}
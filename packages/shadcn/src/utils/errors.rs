@@ -1,3 +1,8 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum ErrorType {
     MissingDirOrEmptyProject,
@@ -13,3 +18,30 @@ pub enum ErrorType {
     ComponentUrlBadRequest,
     ComponentUrlInternalServerError,
 }
+
+impl Display for ErrorType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ErrorType::MissingDirOrEmptyProject => "missing directory or empty project",
+                ErrorType::ExistingConfig => "existing configuration",
+                ErrorType::MissingConfig => "missing configuration",
+                ErrorType::FailedConfigRead => "failed to read configuration",
+                ErrorType::TailwindNotConfigured => "Tailwind CSS not configured",
+                ErrorType::ImportAliasMissing => "import alias missing",
+                ErrorType::UnsupportedFramework => "unsupported framework",
+                ErrorType::ComponentUrlNotFound => "component URL not found",
+                ErrorType::ComponentUrlUnauthorized => "component URL unauthorized",
+                ErrorType::ComponentUrlForbidden => "component URL forbidden",
+                ErrorType::ComponentUrlBadRequest => "component URL bad request",
+                ErrorType::ComponentUrlInternalServerError => {
+                    "component URL internal server error"
+                }
+            }
+        )
+    }
+}
+
+impl Error for ErrorType {}
@@ -0,0 +1,295 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+use shadcn_registry::{registry_base_colors::BASE_COLORS, registry_styles::STYLES, schema::Style};
+
+use crate::utils::{
+    errors::ErrorType,
+    plan::{canonicalize, path_exists, read_file, write_file},
+};
+
+pub const CONFIG_FILE_NAME: &str = "components.toml";
+
+pub const DEFAULT_STYLE: Style = Style::Default;
+pub const DEFAULT_TAILWIND_CONFIG: &str = "tailwind.config.js";
+pub const DEFAULT_TAILWIND_CSS: &str = "src/styles/global.css";
+pub const DEFAULT_TAILWIND_BASE_COLOR: &str = "slate";
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RawConfig {
+    pub style: Style,
+    pub tailwind: TailwindConfig,
+    #[serde(default, skip_serializing_if = "PathsConfig::is_empty")]
+    pub paths: PathsConfig,
+    #[serde(default, skip_serializing_if = "RegistryConfig::is_empty")]
+    pub registry: RegistryConfig,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TailwindConfig {
+    pub config: String,
+    pub css: String,
+    pub base_color: String,
+    pub css_variables: bool,
+    #[serde(default)]
+    pub prefix: String,
+}
+
+/// Optional overrides for the directories components are installed into, relative to the project root.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PathsConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ui: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lib: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hooks: Option<String>,
+}
+
+impl PathsConfig {
+    fn is_empty(&self) -> bool {
+        self.components.is_none() && self.ui.is_none() && self.lib.is_none() && self.hooks.is_none()
+    }
+}
+
+/// Options for fetching from the registry.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RegistryConfig {
+    /// HTTP headers sent to the registry, e.g. `Authorization = "Bearer ${REGISTRY_TOKEN}"`.
+    /// `${VAR}` is replaced with the value of the environment variable `VAR`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+}
+
+impl RegistryConfig {
+    fn is_empty(&self) -> bool {
+        self.headers.is_empty()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ResolvedPaths {
+    pub cwd: PathBuf,
+    pub tailwind_config: PathBuf,
+    pub tailwind_css: PathBuf,
+    pub components: PathBuf,
+    pub ui: PathBuf,
+    pub lib: PathBuf,
+    pub hooks: PathBuf,
+}
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub style: Style,
+    pub tailwind: TailwindConfig,
+    pub paths: PathsConfig,
+    pub registry: RegistryConfig,
+    pub resolved_paths: ResolvedPaths,
+}
+
+impl From<&Config> for RawConfig {
+    fn from(config: &Config) -> Self {
+        RawConfig {
+            style: config.style,
+            tailwind: config.tailwind.clone(),
+            paths: config.paths.clone(),
+            registry: config.registry.clone(),
+        }
+    }
+}
+
+/// Load, validate and resolve `components.toml` in `cwd`.
+///
+/// Errors carry an [`ErrorType`] which can be retrieved with [`anyhow::Error::downcast_ref`].
+pub async fn get_config(cwd: &Path) -> Result<Config> {
+    let config = get_raw_config(cwd).await?;
+
+    resolve_config_paths(cwd, config).await
+}
+
+pub async fn resolve_config_paths(cwd: &Path, config: RawConfig) -> Result<Config> {
+    let cwd = canonicalize(cwd)
+        .await
+        .context(ErrorType::FailedConfigRead)
+        .with_context(|| format!("Failed to resolve {}.", cwd.display()))?;
+
+    let src = if path_exists(&cwd.join("src")).await? {
+        cwd.join("src")
+    } else {
+        cwd.clone()
+    };
+    let resolve = |path: &Option<String>, default: PathBuf| {
+        path.as_ref().map(|path| cwd.join(path)).unwrap_or(default)
+    };
+
+    // A `lib` module of a library crate would be ambiguous with its crate root `src/lib.rs`.
+    let is_library = path_exists(&src.join("lib.rs")).await?;
+    let lib = if is_library {
+        src.join("common")
+    } else {
+        src.join("lib")
+    };
+
+    let components = resolve(&config.paths.components, src.join("components"));
+    let resolved_paths = ResolvedPaths {
+        tailwind_config: cwd.join(&config.tailwind.config),
+        tailwind_css: cwd.join(&config.tailwind.css),
+        ui: resolve(&config.paths.ui, components.join("ui")),
+        lib: resolve(&config.paths.lib, lib),
+        hooks: resolve(&config.paths.hooks, src.join("hooks")),
+        components,
+        cwd,
+    };
+
+    for (name, crate_root) in [("lib", "lib.rs"), ("main", "main.rs")] {
+        let directory = src.join(name);
+        if !path_exists(&src.join(crate_root)).await? {
+            continue;
+        }
+
+        for (key, path) in [
+            ("components", &resolved_paths.components),
+            ("ui", &resolved_paths.ui),
+            ("lib", &resolved_paths.lib),
+            ("hooks", &resolved_paths.hooks),
+        ] {
+            if path.starts_with(&directory) {
+                return Err(anyhow!(
+                    "The path `paths.{key}` is in {}, but a `{name}` module would be ambiguous with the crate root {}. Please choose another directory.",
+                    directory.display(),
+                    src.join(crate_root).display()
+                ))
+                .context(ErrorType::FailedConfigRead)
+                .context(format!(
+                    "Invalid configuration found in {}.",
+                    resolved_paths.cwd.join(CONFIG_FILE_NAME).display()
+                ));
+            }
+        }
+    }
+
+    Ok(Config {
+        style: config.style,
+        tailwind: config.tailwind,
+        paths: config.paths,
+        registry: config.registry,
+        resolved_paths,
+    })
+}
+
+pub async fn get_raw_config(cwd: &Path) -> Result<RawConfig> {
+    let path = cwd.join(CONFIG_FILE_NAME);
+    if !path_exists(&path).await? {
+        return Err(anyhow!(ErrorType::MissingConfig).context(format!(
+            "No {CONFIG_FILE_NAME} file found at {}.",
+            cwd.display()
+        )));
+    }
+
+    let content = read_file(&path)
+        .await
+        .context(ErrorType::FailedConfigRead)
+        .with_context(|| format!("Failed to read {}.", path.display()))?;
+    let config: RawConfig = toml::from_str(&content)
+        .context(ErrorType::FailedConfigRead)
+        .with_context(|| format!("Invalid configuration found in {}.", path.display()))?;
+
+    validate_config(&config)
+        .context(ErrorType::FailedConfigRead)
+        .with_context(|| format!("Invalid configuration found in {}.", path.display()))?;
+
+    Ok(config)
+}
+
+/// Validate and write `components.toml` in `cwd`.
+pub async fn write_config(cwd: &Path, config: &RawConfig) -> Result<()> {
+    validate_config(config)?;
+
+    let content = toml::to_string_pretty(config)?;
+    write_file(&cwd.join(CONFIG_FILE_NAME), content).await
+}
+
+fn validate_config(config: &RawConfig) -> Result<()> {
+    if !STYLES.iter().any(|style| style.name == config.style) {
+        return Err(anyhow!(
+            "Unknown style `{}`. Available styles: {}.",
+            config.style,
+            STYLES
+                .iter()
+                .map(|style| style.name.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    if !BASE_COLORS
+        .iter()
+        .any(|base_color| base_color.name == config.tailwind.base_color)
+    {
+        return Err(anyhow!(
+            "Unknown base color `{}`. Available base colors: {}.",
+            config.tailwind.base_color,
+            BASE_COLORS
+                .iter()
+                .map(|base_color| base_color.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::{TempDir, raw_config};
+
+    #[tokio::test]
+    async fn resolves_lib_path_by_crate_type() {
+        let dir = TempDir::new();
+        dir.write("src/main.rs", "fn main() {}\n");
+        let config = resolve_config_paths(dir.path(), raw_config(PathsConfig::default()))
+            .await
+            .unwrap();
+        assert_eq!(config.resolved_paths.lib, dir.path().join("src/lib"));
+
+        dir.write("src/lib.rs", "");
+        let config = resolve_config_paths(dir.path(), raw_config(PathsConfig::default()))
+            .await
+            .unwrap();
+        assert_eq!(config.resolved_paths.lib, dir.path().join("src/common"));
+    }
+
+    #[tokio::test]
+    async fn rejects_paths_ambiguous_with_crate_root() {
+        let dir = TempDir::new();
+        dir.write("src/lib.rs", "");
+
+        let error = resolve_config_paths(
+            dir.path(),
+            raw_config(PathsConfig {
+                lib: Some("src/lib".into()),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref::<ErrorType>(),
+            Some(ErrorType::FailedConfigRead)
+        ));
+        assert!(error.root_cause().to_string().contains("`paths.lib`"));
+    }
+}
@@ -1,25 +1,598 @@
-use std::path::{Path, PathBuf};
+use std::{
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
 
 use anyhow::Result;
+use regex::Regex;
+use shadcn_registry::{registry_frameworks::FRAMEWORKS, schema::Framework};
+
+use crate::utils::plan::{path_exists, read_dir, read_file};
+
+const PROJECT_SHARED_IGNORE: [&str; 5] = [".git", "dist", "node_modules", "public", "target"];
+
+const TAILWIND_CONFIG_FILES: [&str; 4] = [
+    "tailwind.config.js",
+    "tailwind.config.cjs",
+    "tailwind.config.mjs",
+    "tailwind.config.ts",
+];
+
+static TRUNK_TAILWIND_LINK_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"<link[^>]*rel="tailwind-css"[^>]*>"#).expect("Regex should be valid.")
+});
+static HREF_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"href="([^"]+)""#).expect("Regex should be valid."));
+static TAILWIND_V4_IMPORT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"@import\s+["']tailwindcss["']"#).expect("Regex should be valid.")
+});
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TailwindVersion {
+    #[default]
+    V3,
+    V4,
+}
 
 pub struct ProjectInfo {
-    // pub framework: Framework,
+    pub framework: Option<Framework>,
     pub is_src_dir: bool,
     // pub is_rsc: bool,
     // pub is_tsx: bool,
     pub tailwind_config_file: Option<PathBuf>,
     pub tailwind_css_file: Option<PathBuf>,
+    pub tailwind_version: TailwindVersion,
     // pub alias_prefix: Option<String>,
 }
 
 pub async fn get_project_info(cwd: &Path) -> Result<ProjectInfo> {
-    // TODO
+    let manifests = get_cargo_manifests(cwd).await?;
+
+    let tailwind_css_file = get_tailwind_css_file(cwd, &manifests).await?;
 
     let r#type = ProjectInfo {
-        is_src_dir: false,
-        tailwind_config_file: None,
-        tailwind_css_file: None,
+        framework: get_framework(&manifests),
+        is_src_dir: path_exists(&cwd.join("src")).await?,
+        tailwind_config_file: get_tailwind_config_file(cwd, &manifests).await?,
+        tailwind_version: get_tailwind_version(cwd, tailwind_css_file.as_deref()).await?,
+        tailwind_css_file,
     };
 
     Ok(r#type)
 }
+
+/// Read the `Cargo.toml` in `cwd` and the manifests of its workspace members.
+async fn get_cargo_manifests(cwd: &Path) -> Result<Vec<toml::Table>> {
+    let path = cwd.join("Cargo.toml");
+    if !path_exists(&path).await? {
+        return Ok(vec![]);
+    }
+
+    let manifest: toml::Table = toml::from_str(&read_file(&path).await?)?;
+
+    let mut manifests = vec![];
+    for member_path in get_workspace_members(cwd, &manifest).await? {
+        manifests.push(toml::from_str(
+            &read_file(&member_path.join("Cargo.toml")).await?,
+        )?);
+    }
+    manifests.insert(0, manifest);
+
+    Ok(manifests)
+}
+
+/// Get the directories of the workspace members declared in the manifest in `cwd`.
+pub async fn get_workspace_members(cwd: &Path, manifest: &toml::Table) -> Result<Vec<PathBuf>> {
+    let workspace = manifest.get("workspace").and_then(|value| value.as_table());
+    let members = get_string_array(workspace, "members");
+    let exclude = get_string_array(workspace, "exclude");
+
+    let mut member_paths = vec![];
+    for member in members {
+        for member_path in expand_glob(cwd, &member).await? {
+            let relative_path = member_path.strip_prefix(cwd).unwrap_or(&member_path);
+            if exclude
+                .iter()
+                .any(|exclude| relative_path == Path::new(exclude))
+            {
+                continue;
+            }
+
+            if path_exists(&member_path.join("Cargo.toml")).await? {
+                member_paths.push(member_path);
+            }
+        }
+    }
+
+    Ok(member_paths)
+}
+
+fn get_framework(manifests: &[toml::Table]) -> Option<Framework> {
+    let dependencies = manifests
+        .iter()
+        .flat_map(get_dependency_names)
+        .collect::<Vec<_>>();
+
+    FRAMEWORKS
+        .iter()
+        .find(|framework| {
+            framework
+                .detect_dependencies
+                .iter()
+                .any(|dependency| dependencies.contains(&dependency.as_str()))
+        })
+        .cloned()
+}
+
+fn get_dependency_names(manifest: &toml::Table) -> Vec<&str> {
+    let mut tables = vec![
+        manifest.get("dependencies"),
+        manifest
+            .get("workspace")
+            .and_then(|workspace| workspace.get("dependencies")),
+    ];
+    if let Some(targets) = manifest.get("target").and_then(|value| value.as_table()) {
+        tables.extend(targets.values().map(|target| target.get("dependencies")));
+    }
+
+    tables
+        .into_iter()
+        .flatten()
+        .filter_map(|value| value.as_table())
+        .flat_map(|dependencies| {
+            dependencies.iter().map(|(name, value)| {
+                // Renamed dependencies are detected by their package name.
+                value
+                    .get("package")
+                    .and_then(|package| package.as_str())
+                    .unwrap_or(name)
+            })
+        })
+        .collect()
+}
+
+async fn get_tailwind_config_file(
+    cwd: &Path,
+    manifests: &[toml::Table],
+) -> Result<Option<PathBuf>> {
+    for hint in get_leptos_metadata(cwd, manifests, "tailwind-config-file").await? {
+        if path_exists(&cwd.join(&hint)).await? {
+            return Ok(Some(hint));
+        }
+    }
+
+    let files = find_files(cwd, 3, &|file_name| {
+        TAILWIND_CONFIG_FILES.contains(&file_name)
+    })
+    .await?;
+
+    Ok(files.into_iter().next())
+}
+
+async fn get_tailwind_css_file(cwd: &Path, manifests: &[toml::Table]) -> Result<Option<PathBuf>> {
+    let mut hints = get_leptos_metadata(cwd, manifests, "tailwind-input-file").await?;
+    hints.extend(get_trunk_tailwind_input_file(cwd).await?);
+
+    for hint in hints {
+        if path_exists(&cwd.join(&hint)).await? {
+            return Ok(Some(hint));
+        }
+    }
+
+    let files = find_files(cwd, 5, &|file_name| {
+        file_name.ends_with(".css") || file_name.ends_with(".scss")
+    })
+    .await?;
+
+    for file in files {
+        let contents = read_file(&cwd.join(&file)).await?;
+        if contents.contains("@tailwind base") || TAILWIND_V4_IMPORT_REGEX.is_match(&contents) {
+            return Ok(Some(file));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Detect Tailwind CSS v4 from the CSS entry point, the version Trunk installs or `package.json`.
+async fn get_tailwind_version(
+    cwd: &Path,
+    tailwind_css_file: Option<&Path>,
+) -> Result<TailwindVersion> {
+    if let Some(css_file) = tailwind_css_file {
+        let contents = read_file(&cwd.join(css_file)).await?;
+        if TAILWIND_V4_IMPORT_REGEX.is_match(&contents) {
+            return Ok(TailwindVersion::V4);
+        }
+    }
+
+    let mut versions = vec![];
+
+    let trunk_path = cwd.join("Trunk.toml");
+    if path_exists(&trunk_path).await? {
+        let trunk_config: toml::Table = toml::from_str(&read_file(&trunk_path).await?)?;
+        versions.extend(
+            trunk_config
+                .get("tools")
+                .and_then(|tools| tools.get("tailwindcss"))
+                .and_then(|version| version.as_str())
+                .map(String::from),
+        );
+    }
+
+    let package_path = cwd.join("package.json");
+    if path_exists(&package_path).await? {
+        let package: serde_json::Value = serde_json::from_str(&read_file(&package_path).await?)?;
+        versions.extend(
+            ["dependencies", "devDependencies"]
+                .into_iter()
+                .filter_map(|key| package.get(key)?.get("tailwindcss")?.as_str())
+                .map(String::from),
+        );
+    }
+
+    let is_v4 = versions.iter().any(|version| {
+        version
+            .trim_start_matches(|char: char| "^~>=v ".contains(char))
+            .split('.')
+            .next()
+            .and_then(|major| major.parse::<u64>().ok())
+            .is_some_and(|major| major >= 4)
+    });
+
+    Ok(if is_v4 {
+        TailwindVersion::V4
+    } else {
+        TailwindVersion::V3
+    })
+}
+
+/// Read a key from the cargo-leptos configuration in `Leptos.toml` or the `[package.metadata.leptos]` and `[[workspace.metadata.leptos]]` tables.
+async fn get_leptos_metadata(
+    cwd: &Path,
+    manifests: &[toml::Table],
+    key: &str,
+) -> Result<Vec<PathBuf>> {
+    let mut tables = vec![];
+
+    let path = cwd.join("Leptos.toml");
+    let leptos_config = if path_exists(&path).await? {
+        Some(toml::from_str::<toml::Table>(&read_file(&path).await?)?)
+    } else {
+        None
+    };
+    if let Some(leptos_config) = &leptos_config {
+        tables.push(leptos_config);
+        tables.extend(
+            leptos_config
+                .get("package")
+                .and_then(|value| value.as_table()),
+        );
+    }
+
+    for manifest in manifests {
+        for section in ["package", "workspace"] {
+            match manifest
+                .get(section)
+                .and_then(|value| value.get("metadata"))
+                .and_then(|value| value.get("leptos"))
+            {
+                Some(toml::Value::Table(table)) => tables.push(table),
+                Some(toml::Value::Array(array)) => {
+                    tables.extend(array.iter().filter_map(|value| value.as_table()))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(tables
+        .into_iter()
+        .filter_map(|table| table.get(key).and_then(|value| value.as_str()))
+        .map(PathBuf::from)
+        .collect())
+}
+
+/// Read the Tailwind CSS input file from the `<link data-trunk rel="tailwind-css">` in Trunk's target HTML file.
+async fn get_trunk_tailwind_input_file(cwd: &Path) -> Result<Option<PathBuf>> {
+    let trunk_path = cwd.join("Trunk.toml");
+    let target = if path_exists(&trunk_path).await? {
+        let trunk_config: toml::Table = toml::from_str(&read_file(&trunk_path).await?)?;
+        trunk_config
+            .get("build")
+            .and_then(|build| build.get("target"))
+            .and_then(|target| target.as_str())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("index.html"))
+    } else {
+        PathBuf::from("index.html")
+    };
+
+    let path = cwd.join(&target);
+    if !path_exists(&path).await? {
+        return Ok(None);
+    }
+
+    let html = read_file(&path).await?;
+    Ok(TRUNK_TAILWIND_LINK_REGEX
+        .find(&html)
+        .and_then(|link| HREF_REGEX.captures(link.as_str()))
+        .map(|captures| target.parent().unwrap_or(Path::new("")).join(&captures[1])))
+}
+
+/// Find files relative to `cwd` up to `depth` directories deep, skipping shared ignored directories.
+async fn find_files(
+    cwd: &Path,
+    depth: usize,
+    predicate: &dyn Fn(&str) -> bool,
+) -> Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    let mut directories = vec![(PathBuf::new(), 0)];
+
+    while let Some((directory, level)) = directories.pop() {
+        for entry in read_dir(&cwd.join(&directory)).await? {
+            let path = directory.join(&entry.name);
+
+            if entry.is_dir {
+                if level + 1 < depth && !PROJECT_SHARED_IGNORE.contains(&entry.name.as_str()) {
+                    directories.push((path, level + 1));
+                }
+            } else if predicate(&entry.name) {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort_by_key(|path| path.components().count());
+
+    Ok(files)
+}
+
+/// Expand a Cargo workspace member pattern, supporting `*` wildcards in path segments.
+async fn expand_glob(cwd: &Path, pattern: &str) -> Result<Vec<PathBuf>> {
+    let mut paths = vec![cwd.to_path_buf()];
+
+    for segment in pattern.split('/') {
+        let mut next = vec![];
+
+        for path in paths {
+            if !segment.contains('*') {
+                next.push(path.join(segment));
+                continue;
+            }
+
+            let (prefix, suffix) = segment.split_once('*').unwrap_or((segment, ""));
+            for entry in read_dir(&path).await? {
+                if entry.is_dir && entry.name.starts_with(prefix) && entry.name.ends_with(suffix) {
+                    next.push(path.join(&entry.name));
+                }
+            }
+        }
+
+        paths = next;
+    }
+
+    paths.sort();
+
+    Ok(paths)
+}
+
+fn get_string_array(table: Option<&toml::Table>, key: &str) -> Vec<String> {
+    table
+        .and_then(|table| table.get(key))
+        .and_then(|value| value.as_array())
+        .map(|array| {
+            array
+                .iter()
+                .filter_map(|value| value.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use shadcn_registry::schema::FrameworkName;
+
+    use super::*;
+    use crate::utils::test_utils::TempDir;
+
+    async fn framework(dir: &TempDir) -> Option<FrameworkName> {
+        get_project_info(dir.path())
+            .await
+            .unwrap()
+            .framework
+            .map(|framework| framework.name)
+    }
+
+    #[tokio::test]
+    async fn detects_framework_from_dependencies() {
+        let dir = TempDir::new();
+        assert_eq!(framework(&dir).await, None);
+
+        dir.write(
+            "Cargo.toml",
+            "[package]\nname = \"app\"\n\n[dependencies]\nserde = \"1\"\n",
+        );
+        assert_eq!(framework(&dir).await, None);
+
+        dir.write("Cargo.toml", "[dependencies]\nleptos = \"0.8\"\n");
+        assert_eq!(framework(&dir).await, Some(FrameworkName::Leptos));
+
+        dir.write(
+            "Cargo.toml",
+            "[dependencies]\nui = { package = \"yew\", version = \"0.21\" }\n",
+        );
+        assert_eq!(framework(&dir).await, Some(FrameworkName::Yew));
+
+        dir.write(
+            "Cargo.toml",
+            "[target.'cfg(target_arch = \"wasm32\")'.dependencies]\ndioxus = \"0.6\"\n",
+        );
+        assert_eq!(framework(&dir).await, Some(FrameworkName::Dioxus));
+    }
+
+    #[tokio::test]
+    async fn detects_framework_in_workspace_members() {
+        let dir = TempDir::new();
+        dir.write(
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"crates/*\", \"app\"]\nexclude = [\"crates/excluded\"]\n",
+        );
+        dir.write("crates/core/Cargo.toml", "[dependencies]\nserde = \"1\"\n");
+        dir.write(
+            "crates/excluded/Cargo.toml",
+            "[dependencies]\nleptos = \"0.8\"\n",
+        );
+        dir.write("crates/assets/style.css", "");
+        dir.write("app/Cargo.toml", "[dependencies]\nyew = \"0.21\"\n");
+
+        let manifest = toml::from_str(&dir.read("Cargo.toml")).unwrap();
+        assert_eq!(
+            get_workspace_members(dir.path(), &manifest).await.unwrap(),
+            [dir.path().join("crates/core"), dir.path().join("app")]
+        );
+        assert_eq!(framework(&dir).await, Some(FrameworkName::Yew));
+
+        dir.write(
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"app\"]\n\n[workspace.dependencies]\nleptos = \"0.8\"\n",
+        );
+        assert_eq!(framework(&dir).await, Some(FrameworkName::Leptos));
+    }
+
+    #[tokio::test]
+    async fn finds_tailwind_files_from_leptos_metadata() {
+        let dir = TempDir::new();
+        dir.write(
+            "Cargo.toml",
+            "[package.metadata.leptos]\ntailwind-input-file = \"style/tailwind.css\"\ntailwind-config-file = \"config/tailwind.config.js\"\n",
+        );
+        dir.write("style/tailwind.css", "@tailwind base;\n");
+        dir.write("config/tailwind.config.js", "");
+        dir.write("main.css", "@tailwind base;\n");
+        dir.write("tailwind.config.ts", "");
+
+        let project_info = get_project_info(dir.path()).await.unwrap();
+        assert_eq!(
+            project_info.tailwind_css_file,
+            Some(PathBuf::from("style/tailwind.css"))
+        );
+        assert_eq!(
+            project_info.tailwind_config_file,
+            Some(PathBuf::from("config/tailwind.config.js"))
+        );
+
+        // Workspaces configure cargo-leptos per project, and `Leptos.toml` takes precedence.
+        dir.write(
+            "Cargo.toml",
+            "[workspace]\n\n[[workspace.metadata.leptos]]\ntailwind-input-file = \"style/tailwind.css\"\n",
+        );
+        assert_eq!(
+            get_project_info(dir.path())
+                .await
+                .unwrap()
+                .tailwind_css_file,
+            Some(PathBuf::from("style/tailwind.css"))
+        );
+
+        dir.write(
+            "Leptos.toml",
+            "[package]\ntailwind-input-file = \"main.css\"\n",
+        );
+        assert_eq!(
+            get_project_info(dir.path())
+                .await
+                .unwrap()
+                .tailwind_css_file,
+            Some(PathBuf::from("main.css"))
+        );
+    }
+
+    #[tokio::test]
+    async fn finds_tailwind_css_from_trunk() {
+        let dir = TempDir::new();
+        dir.write(
+            "index.html",
+            "<head><link data-trunk rel=\"tailwind-css\" href=\"input.css\" /></head>\n",
+        );
+        dir.write("input.css", "");
+        assert_eq!(
+            get_project_info(dir.path())
+                .await
+                .unwrap()
+                .tailwind_css_file,
+            Some(PathBuf::from("input.css"))
+        );
+
+        dir.write("Trunk.toml", "[build]\ntarget = \"web/index.html\"\n");
+        dir.write(
+            "web/index.html",
+            "<link rel=\"tailwind-css\" data-trunk href=\"styles/main.css\">\n",
+        );
+        dir.write("web/styles/main.css", "");
+        assert_eq!(
+            get_project_info(dir.path())
+                .await
+                .unwrap()
+                .tailwind_css_file,
+            Some(PathBuf::from("web/styles/main.css"))
+        );
+    }
+
+    #[tokio::test]
+    async fn scans_for_tailwind_files() {
+        let dir = TempDir::new();
+        dir.write("reset.css", "* { margin: 0; }\n");
+        dir.write("node_modules/theme/tailwind.css", "@tailwind base;\n");
+        dir.write("src/styles/global.scss", "@tailwind base;\n");
+        dir.write("target/site/tailwind.config.js", "");
+        dir.write("app/tailwind.config.cjs", "");
+
+        let project_info = get_project_info(dir.path()).await.unwrap();
+        assert!(project_info.is_src_dir);
+        assert_eq!(
+            project_info.tailwind_css_file,
+            Some(PathBuf::from("src/styles/global.scss"))
+        );
+        assert_eq!(
+            project_info.tailwind_config_file,
+            Some(PathBuf::from("app/tailwind.config.cjs"))
+        );
+
+        let dir = TempDir::new();
+        let project_info = get_project_info(dir.path()).await.unwrap();
+        assert!(!project_info.is_src_dir);
+        assert_eq!(project_info.tailwind_css_file, None);
+        assert_eq!(project_info.tailwind_config_file, None);
+    }
+
+    #[tokio::test]
+    async fn detects_tailwind_version() {
+        let version =
+            async |dir: &TempDir| get_project_info(dir.path()).await.unwrap().tailwind_version;
+
+        let dir = TempDir::new();
+        assert_eq!(version(&dir).await, TailwindVersion::V3);
+        dir.write("style/main.css", "@tailwind base;\n");
+        dir.write(
+            "package.json",
+            r#"{"devDependencies":{"tailwindcss":"^3.4.0"}}"#,
+        );
+        assert_eq!(version(&dir).await, TailwindVersion::V3);
+
+        dir.write(
+            "package.json",
+            r#"{"devDependencies":{"tailwindcss":"^4.0.0"}}"#,
+        );
+        assert_eq!(version(&dir).await, TailwindVersion::V4);
+
+        let dir = TempDir::new();
+        dir.write("Trunk.toml", "[tools]\ntailwindcss = \"4.1.3\"\n");
+        assert_eq!(version(&dir).await, TailwindVersion::V4);
+
+        let dir = TempDir::new();
+        dir.write("style/main.css", "@import 'tailwindcss';\n");
+        assert_eq!(version(&dir).await, TailwindVersion::V4);
+    }
+}
@@ -1,5 +1,12 @@
 use std::sync::LazyLock;
 
+use syntect::{
+    easy::HighlightLines,
+    highlighting::ThemeSet,
+    parsing::SyntaxSet,
+    util::{LinesWithEndings, as_24_bit_terminal_escaped},
+};
+
 // Based on https://github.com/lukeed/kleur.
 struct Style {
     open: String,
@@ -79,3 +86,25 @@ impl Highlighter {
         self.success.format(text)
     }
 }
+
+static SYNTAX_SET: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+static THEME_SET: LazyLock<ThemeSet> = LazyLock::new(ThemeSet::load_defaults);
+
+/// Highlight source code for the terminal, detecting the language from the file extension.
+pub fn highlight_code(code: &str, extension: &str) -> String {
+    let syntax = SYNTAX_SET
+        .find_syntax_by_extension(extension)
+        .unwrap_or_else(|| SYNTAX_SET.find_syntax_plain_text());
+    let mut highlighter = HighlightLines::new(syntax, &THEME_SET.themes["base16-ocean.dark"]);
+
+    let mut output = String::new();
+    for line in LinesWithEndings::from(code) {
+        match highlighter.highlight_line(line, &SYNTAX_SET) {
+            Ok(ranges) => output.push_str(&as_24_bit_terminal_escaped(&ranges, false)),
+            Err(_) => output.push_str(line),
+        }
+    }
+    output.push_str("\x1b[0m");
+
+    output
+}
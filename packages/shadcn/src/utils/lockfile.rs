@@ -0,0 +1,315 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use shadcn_registry::schema::{
+    FrameworkName, RegistryEntry, RegistryItemFile, RegistryItemType, Style,
+};
+
+use crate::utils::{
+    get_config::Config,
+    plan::{path_exists, read_file, write_file},
+    registry::{get_registry_item_file_path, get_registry_item_url},
+};
+
+pub const LOCKFILE_NAME: &str = "components.lock";
+
+const LOCKFILE_VERSION: u32 = 1;
+const LOCKFILE_HEADER: &str =
+    "# This file is generated by rust-shadcn. It is not intended for manual editing.\n\n";
+
+/// Registry items installed in the project, written by `init` and `add`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Lockfile {
+    pub version: u32,
+    #[serde(default, rename = "item", skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<LockedItem>,
+}
+
+impl Default for Lockfile {
+    fn default() -> Self {
+        Lockfile {
+            version: LOCKFILE_VERSION,
+            items: vec![],
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LockedItem {
+    pub name: String,
+    pub r#type: RegistryItemType,
+    pub framework: FrameworkName,
+    pub style: Style,
+    /// The registry URL the item was fetched from.
+    pub registry: String,
+    /// The alias for the item's public identifiers, e.g. `FancyButton`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    /// The crates the item depends on.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<String>,
+    /// The registry items the item depends on.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub registry_dependencies: Vec<String>,
+    #[serde(default, rename = "file", skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<LockedFile>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LockedFile {
+    /// The path in the project, relative to the project root.
+    pub path: String,
+    /// The path in the registry item.
+    pub registry_path: String,
+    /// Hash of the content as installed from the registry, e.g. `sha256:...`.
+    pub hash: String,
+    /// Whether `update` left conflict markers in the file which are not resolved yet.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub conflicted: bool,
+}
+
+impl Lockfile {
+    pub fn get(&self, name: &str) -> Option<&LockedItem> {
+        self.items.iter().find(|item| item.name == name)
+    }
+
+    /// Add or replace an item, keeping items sorted by name.
+    pub fn insert(&mut self, item: LockedItem) {
+        self.items.retain(|existing| existing.name != item.name);
+        self.items.push(item);
+        self.items.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    /// The aliases the items were installed with, e.g. `button` to `FancyButton`.
+    pub fn aliases(&self) -> HashMap<String, String> {
+        self.items
+            .iter()
+            .filter_map(|item| Some((item.name.clone(), item.alias.clone()?)))
+            .collect()
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<LockedItem> {
+        let index = self.items.iter().position(|item| item.name == name)?;
+
+        Some(self.items.remove(index))
+    }
+}
+
+impl LockedItem {
+    /// Record an item as installed, with its files at the paths given by `file_path`.
+    pub fn new<F>(
+        item: &RegistryEntry,
+        framework: FrameworkName,
+        alias: Option<String>,
+        config: &Config,
+        file_path: F,
+    ) -> Self
+    where
+        F: Fn(&RegistryItemFile) -> PathBuf,
+    {
+        LockedItem {
+            name: item.name.clone(),
+            r#type: item.r#type,
+            framework,
+            style: config.style,
+            registry: get_registry_item_url(&item.name, framework, config.style),
+            alias,
+            dependencies: item
+                .dependencies
+                .iter()
+                .flatten()
+                .map(|dependency| dependency.to_crate().name)
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+            registry_dependencies: item.registry_dependencies.clone().unwrap_or_default(),
+            files: item
+                .files
+                .iter()
+                .flatten()
+                .filter_map(|file| {
+                    let content = file.content.as_ref()?;
+
+                    Some(LockedFile {
+                        path: lockfile_path(&file_path(file), config),
+                        registry_path: file.path.clone(),
+                        hash: hash_content(content),
+                        conflicted: false,
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    pub fn get_file(&self, registry_path: &str) -> Option<&LockedFile> {
+        self.files
+            .iter()
+            .find(|file| file.registry_path == registry_path)
+    }
+}
+
+/// Hash file content for the lockfile.
+pub fn hash_content(content: &str) -> String {
+    format!("sha256:{:x}", Sha256::digest(content.as_bytes()))
+}
+
+/// Read the lockfile, or an empty lockfile if it does not exist.
+pub async fn read_lockfile(config: &Config) -> Result<Lockfile> {
+    let path = config.resolved_paths.cwd.join(LOCKFILE_NAME);
+    if !path_exists(&path).await? {
+        return Ok(Lockfile::default());
+    }
+
+    let content = read_file(&path).await?;
+    toml::from_str(&content).with_context(|| format!("Invalid lockfile {}.", path.display()))
+}
+
+pub async fn write_lockfile(config: &Config, lockfile: &Lockfile) -> Result<()> {
+    let content = toml::to_string_pretty(lockfile)?;
+    write_file(
+        &config.resolved_paths.cwd.join(LOCKFILE_NAME),
+        format!("{LOCKFILE_HEADER}{content}"),
+    )
+    .await
+}
+
+/// A locked file which does not match the content installed from the registry.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LockfileMismatch {
+    Missing(String),
+    Modified(String),
+}
+
+/// Verify the installed files against the hashes recorded in the lockfile, without fetching the registry.
+pub async fn verify_lockfile(
+    lockfile: &Lockfile,
+    config: &Config,
+) -> Result<Vec<LockfileMismatch>> {
+    let mut mismatches = vec![];
+    for file in lockfile.items.iter().flat_map(|item| &item.files) {
+        let file_path = config.resolved_paths.cwd.join(&file.path);
+        if !path_exists(&file_path).await? {
+            mismatches.push(LockfileMismatch::Missing(file.path.clone()));
+            continue;
+        }
+
+        let content = read_file(&file_path).await?;
+        if hash_content(&content) != file.hash {
+            mismatches.push(LockfileMismatch::Modified(file.path.clone()));
+        }
+    }
+
+    Ok(mismatches)
+}
+
+/// Format a path relative to the project root for the lockfile, with `/` separators on all platforms.
+pub fn lockfile_path(file_path: &Path, config: &Config) -> String {
+    file_path
+        .strip_prefix(&config.resolved_paths.cwd)
+        .unwrap_or(file_path)
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Get the path a registry file is installed to, preferring the path recorded in the lockfile.
+pub fn get_installed_file_path(
+    file: &RegistryItemFile,
+    locked_item: Option<&LockedItem>,
+    config: &Config,
+) -> PathBuf {
+    match locked_item.and_then(|item| item.get_file(&file.path)) {
+        Some(locked_file) => config.resolved_paths.cwd.join(&locked_file.path),
+        None => get_registry_item_file_path(file, config, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::{TempDir, config};
+
+    fn locked_item(name: &str, files: &[(&str, &str)]) -> LockedItem {
+        LockedItem {
+            name: name.into(),
+            r#type: RegistryItemType::Ui,
+            framework: FrameworkName::Leptos,
+            style: Style::Default,
+            registry: format!("https://example.com/{name}.json"),
+            alias: None,
+            dependencies: vec![],
+            registry_dependencies: vec![],
+            files: files
+                .iter()
+                .map(|(path, content)| LockedFile {
+                    path: (*path).into(),
+                    registry_path: (*path).into(),
+                    hash: hash_content(content),
+                    conflicted: false,
+                })
+                .collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn verifies_installed_files() {
+        let dir = TempDir::new();
+        dir.write("src/components/ui/button.rs", "pub struct Button;\n");
+        dir.write(
+            "src/components/ui/card.rs",
+            "pub struct Card;\n// Local change.\n",
+        );
+        let config = config(dir.path()).await;
+
+        let mut lockfile = Lockfile::default();
+        lockfile.insert(locked_item(
+            "button",
+            &[("src/components/ui/button.rs", "pub struct Button;\n")],
+        ));
+        lockfile.insert(locked_item(
+            "card",
+            &[("src/components/ui/card.rs", "pub struct Card;\n")],
+        ));
+        lockfile.insert(locked_item(
+            "input",
+            &[("src/components/ui/input.rs", "pub struct Input;\n")],
+        ));
+
+        assert_eq!(
+            verify_lockfile(&lockfile, &config).await.unwrap(),
+            vec![
+                LockfileMismatch::Modified("src/components/ui/card.rs".into()),
+                LockfileMismatch::Missing("src/components/ui/input.rs".into()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn roundtrips_lockfile() {
+        let dir = TempDir::new();
+        let config = config(dir.path()).await;
+
+        let mut lockfile = Lockfile::default();
+        lockfile.insert(locked_item("card", &[("src/card.rs", "")]));
+        lockfile.insert(locked_item("button", &[("src/button.rs", "")]));
+        write_lockfile(&config, &lockfile).await.unwrap();
+
+        let content = dir.read(LOCKFILE_NAME);
+        assert!(content.starts_with(LOCKFILE_HEADER));
+        let lockfile = read_lockfile(&config).await.unwrap();
+        assert_eq!(
+            lockfile
+                .items
+                .iter()
+                .map(|item| item.name.as_str())
+                .collect::<Vec<_>>(),
+            ["button", "card"]
+        );
+    }
+}
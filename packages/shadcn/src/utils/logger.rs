@@ -1,37 +1,61 @@
-use std::sync::LazyLock;
+use std::sync::{
+    LazyLock,
+    atomic::{AtomicBool, Ordering},
+};
 
 use super::highlighter::HIGHLIGHTER;
 
 pub static LOGGER: LazyLock<Logger> = LazyLock::new(Logger::new);
 
-pub struct Logger;
+pub struct Logger {
+    stderr: AtomicBool,
+}
 
 impl Logger {
     fn new() -> Self {
-        Self
+        Self {
+            stderr: AtomicBool::new(false),
+        }
+    }
+
+    /// Write logs to stderr instead of stdout, e.g. to keep stdout for JSON output.
+    pub fn use_stderr(&self) {
+        self.stderr.store(true, Ordering::Relaxed);
+    }
+
+    pub fn uses_stderr(&self) -> bool {
+        self.stderr.load(Ordering::Relaxed)
+    }
+
+    fn print(&self, text: &str) {
+        if self.uses_stderr() {
+            eprintln!("{text}");
+        } else {
+            println!("{text}");
+        }
     }
 
     pub fn error(&self, text: &str) {
-        println!("{}", HIGHLIGHTER.error(text));
+        self.print(&HIGHLIGHTER.error(text));
     }
 
     pub fn warn(&self, text: &str) {
-        println!("{}", HIGHLIGHTER.warn(text));
+        self.print(&HIGHLIGHTER.warn(text));
     }
 
     pub fn info(&self, text: &str) {
-        println!("{}", HIGHLIGHTER.info(text));
+        self.print(&HIGHLIGHTER.info(text));
     }
 
     pub fn success(&self, text: &str) {
-        println!("{}", HIGHLIGHTER.success(text));
+        self.print(&HIGHLIGHTER.success(text));
     }
 
     pub fn log(&self, text: &str) {
-        println!("{text}");
+        self.print(text);
     }
 
     pub fn r#break(&self) {
-        println!();
+        self.print("");
     }
 }
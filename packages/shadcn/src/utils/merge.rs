@@ -0,0 +1,180 @@
+use similar::{Algorithm, DiffOp, capture_diff_slices};
+
+pub const CONFLICT_START: &str = "<<<<<<< local";
+pub const CONFLICT_SEPARATOR: &str = "=======";
+pub const CONFLICT_END: &str = ">>>>>>> registry";
+
+pub struct MergeResult {
+    pub content: String,
+    pub conflicts: usize,
+}
+
+/// Merge the changes from `base` to `local` and from `base` to `remote` line by line.
+///
+/// Lines changed on both sides in different ways are left as conflicts between Git-style markers.
+pub fn merge(base: &str, local: &str, remote: &str) -> MergeResult {
+    let base = base.split_inclusive('\n').collect::<Vec<_>>();
+    let local = local.split_inclusive('\n').collect::<Vec<_>>();
+    let remote = remote.split_inclusive('\n').collect::<Vec<_>>();
+
+    let local_matches = matches(&base, &local);
+    let remote_matches = matches(&base, &remote);
+
+    let mut lines: Vec<&str> = vec![];
+    let mut conflicts = 0;
+    let (mut base_index, mut local_index, mut remote_index) = (0, 0, 0);
+
+    loop {
+        // Find the next base line which is unchanged on both sides.
+        let stable = (base_index..base.len())
+            .find_map(|index| Some((index, local_matches[index]?, remote_matches[index]?)));
+
+        let (base_end, local_end, remote_end) =
+            stable.unwrap_or((base.len(), local.len(), remote.len()));
+
+        if (base_end, local_end, remote_end) == (base_index, local_index, remote_index) {
+            if stable.is_none() {
+                break;
+            }
+
+            lines.push(base[base_index]);
+            base_index += 1;
+            local_index += 1;
+            remote_index += 1;
+            continue;
+        }
+
+        let base_chunk = &base[base_index..base_end];
+        let local_chunk = &local[local_index..local_end];
+        let remote_chunk = &remote[remote_index..remote_end];
+
+        if local_chunk == base_chunk || local_chunk == remote_chunk {
+            lines.extend(remote_chunk);
+        } else if remote_chunk == base_chunk {
+            lines.extend(local_chunk);
+        } else {
+            conflicts += 1;
+
+            lines.push(CONFLICT_START);
+            lines.push("\n");
+            push_lines(&mut lines, local_chunk);
+            lines.push(CONFLICT_SEPARATOR);
+            lines.push("\n");
+            push_lines(&mut lines, remote_chunk);
+            lines.push(CONFLICT_END);
+            lines.push("\n");
+        }
+
+        (base_index, local_index, remote_index) = (base_end, local_end, remote_end);
+    }
+
+    MergeResult {
+        content: lines.concat(),
+        conflicts,
+    }
+}
+
+/// Whether the content contains conflict markers left by [`merge`].
+pub fn has_conflict_markers(content: &str) -> bool {
+    let mut lines = content.lines();
+
+    lines.any(|line| line.starts_with(CONFLICT_START))
+        && lines.any(|line| line.starts_with(CONFLICT_END))
+}
+
+/// Map each line of `old` to the matching line of `new`, if it is unchanged.
+fn matches(old: &[&str], new: &[&str]) -> Vec<Option<usize>> {
+    let mut matches = vec![None; old.len()];
+
+    for op in capture_diff_slices(Algorithm::Myers, old, new) {
+        if let DiffOp::Equal {
+            old_index,
+            new_index,
+            len,
+        } = op
+        {
+            for offset in 0..len {
+                matches[old_index + offset] = Some(new_index + offset);
+            }
+        }
+    }
+
+    matches
+}
+
+/// Push lines inside a conflict, ensuring the last line ends before the next marker.
+fn push_lines<'a>(lines: &mut Vec<&'a str>, chunk: &[&'a str]) {
+    lines.extend(chunk);
+    if chunk.last().is_some_and(|line| !line.ends_with('\n')) {
+        lines.push("\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = "fn a() {}\n\nfn b() {}\n\nfn c() {}\n";
+
+    #[test]
+    fn applies_remote_changes() {
+        let remote = "fn a() {}\n\nfn b() -> u8 {}\n\nfn c() {}\n";
+
+        let result = merge(BASE, BASE, remote);
+
+        assert_eq!(result.content, remote);
+        assert_eq!(result.conflicts, 0);
+    }
+
+    #[test]
+    fn keeps_local_changes() {
+        let local = "fn a() {}\n\nfn b() {}\n\nfn c(x: u8) {}\n";
+
+        let result = merge(BASE, local, BASE);
+
+        assert_eq!(result.content, local);
+        assert_eq!(result.conflicts, 0);
+    }
+
+    #[test]
+    fn merges_changes_to_different_lines() {
+        let local = "fn a(x: u8) {}\n\nfn b() {}\n\nfn c() {}\n";
+        let remote = "fn a() {}\n\nfn b() {}\n\nfn c() -> u8 {}\nfn d() {}\n";
+
+        let result = merge(BASE, local, remote);
+
+        assert_eq!(
+            result.content,
+            "fn a(x: u8) {}\n\nfn b() {}\n\nfn c() -> u8 {}\nfn d() {}\n"
+        );
+        assert_eq!(result.conflicts, 0);
+        assert!(!has_conflict_markers(&result.content));
+    }
+
+    #[test]
+    fn merges_identical_changes() {
+        let changed = "fn a() {}\n\nfn b() -> u8 {}\n\nfn c() {}\n";
+
+        let result = merge(BASE, changed, changed);
+
+        assert_eq!(result.content, changed);
+        assert_eq!(result.conflicts, 0);
+    }
+
+    #[test]
+    fn marks_conflicts() {
+        let local = "fn a() {}\n\nfn b(x: u8) {}\n\nfn c() {}";
+        let remote = "fn a() {}\n\nfn b() -> u8 {}\n\nfn c() {}";
+
+        let result = merge(BASE, local, remote);
+
+        assert_eq!(
+            result.content,
+            format!(
+                "fn a() {{}}\n\n{CONFLICT_START}\nfn b(x: u8) {{}}\n{CONFLICT_SEPARATOR}\nfn b() -> u8 {{}}\n{CONFLICT_END}\n\nfn c() {{}}"
+            )
+        );
+        assert_eq!(result.conflicts, 1);
+        assert!(has_conflict_markers(&result.content));
+    }
+}
@@ -0,0 +1,463 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use tokio::fs;
+
+use crate::utils::{
+    get_config::Config,
+    plan::{path_exists, read_file, write_file},
+};
+
+/// Where the module file of a directory is, e.g. `ui/mod.rs` or `ui.rs` for `ui/`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ModuleLayout {
+    #[default]
+    ModRs,
+    NonModRs,
+}
+
+#[derive(Debug, Default)]
+pub struct ModuleChanges {
+    pub created: Vec<PathBuf>,
+    pub updated: Vec<PathBuf>,
+}
+
+/// Detect the module layout of the project, preferring `mod.rs` files if neither is used.
+pub async fn get_module_layout(config: &Config) -> Result<ModuleLayout> {
+    let src = get_src_dir(config);
+    if !fs::try_exists(&src).await? {
+        return Ok(ModuleLayout::default());
+    }
+
+    let (mut mod_rs, mut non_mod_rs) = (0, 0);
+    let mut directories = vec![src.clone()];
+    while let Some(directory) = directories.pop() {
+        let mut entries = fs::read_dir(&directory).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if entry.file_type().await?.is_dir() {
+                if new_module_file(&path, &src, ModuleLayout::NonModRs) != path.join("mod.rs")
+                    && fs::try_exists(path.with_extension("rs")).await?
+                {
+                    non_mod_rs += 1;
+                }
+                directories.push(path);
+            } else if entry.file_name() == "mod.rs"
+                // `src/lib/mod.rs` and `src/main/mod.rs` are required by either layout.
+                && new_module_file(&directory, &src, ModuleLayout::NonModRs) != path
+            {
+                mod_rs += 1;
+            }
+        }
+    }
+
+    Ok(if non_mod_rs > mod_rs {
+        ModuleLayout::NonModRs
+    } else {
+        ModuleLayout::ModRs
+    })
+}
+
+/// Declare the modules of the files in their parent modules, up to the crate root.
+///
+/// Missing module files are created with the given layout. Existing declarations and other items are kept.
+pub async fn add_module_declarations(
+    file_paths: &[PathBuf],
+    config: &Config,
+    layout: ModuleLayout,
+) -> Result<ModuleChanges> {
+    let src = get_src_dir(config);
+    let mut changes = ModuleChanges::default();
+
+    for file_path in file_paths {
+        if !file_path.starts_with(&src) {
+            continue;
+        }
+
+        let mut current = file_path.clone();
+        while let Some((name, directory)) = module_of(&current, &src) {
+            let module_file = match find_module_file(directory, &src).await? {
+                Some(module_file) => module_file,
+                // The crate root is never created.
+                None if directory == src => break,
+                None => new_module_file(directory, &src, layout),
+            };
+
+            let exists = path_exists(&module_file).await?;
+            let content = if exists {
+                read_file(&module_file).await?
+            } else {
+                String::new()
+            };
+
+            if let Some(updated_content) = insert_module_declaration(&content, name) {
+                write_file(&module_file, updated_content).await?;
+
+                let relative_path = module_file
+                    .strip_prefix(&config.resolved_paths.cwd)
+                    .unwrap_or(&module_file)
+                    .to_path_buf();
+                if !exists {
+                    changes.created.push(relative_path);
+                } else if !changes.created.contains(&relative_path)
+                    && !changes.updated.contains(&relative_path)
+                {
+                    changes.updated.push(relative_path);
+                }
+            }
+
+            current = module_file;
+        }
+    }
+
+    Ok(changes)
+}
+
+/// Remove the declaration of the module defined by `file_path` from its parent module.
+///
+/// Returns the parent module if it was changed.
+pub async fn remove_module_declaration(
+    file_path: &Path,
+    config: &Config,
+) -> Result<Option<PathBuf>> {
+    let src = get_src_dir(config);
+    let Some((name, directory)) = module_of(file_path, &src) else {
+        return Ok(None);
+    };
+    let Some(module_file) = find_module_file(directory, &src).await? else {
+        return Ok(None);
+    };
+
+    let content = read_file(&module_file).await?;
+    let Some(updated_content) = remove_declaration(&content, name) else {
+        return Ok(None);
+    };
+    write_file(&module_file, updated_content).await?;
+
+    Ok(Some(module_file))
+}
+
+fn get_src_dir(config: &Config) -> PathBuf {
+    config.resolved_paths.cwd.join("src")
+}
+
+/// Get the name of the module defined by a file and the directory whose module declares it.
+fn module_of<'a>(file_path: &'a Path, src: &Path) -> Option<(&'a str, &'a Path)> {
+    if file_path
+        .extension()
+        .is_none_or(|extension| extension != "rs")
+    {
+        return None;
+    }
+
+    let directory = file_path.parent()?;
+    match file_path.file_stem()?.to_str()? {
+        "lib" | "main" if directory == src => None,
+        "mod" if directory == src => None,
+        "mod" => Some((directory.file_name()?.to_str()?, directory.parent()?)),
+        name => Some((name, directory)),
+    }
+}
+
+/// Find the existing module file of a directory, i.e. the crate root for `src`.
+async fn find_module_file(directory: &Path, src: &Path) -> Result<Option<PathBuf>> {
+    let candidates = if directory == src {
+        vec![src.join("lib.rs"), src.join("main.rs")]
+    } else {
+        vec![
+            new_module_file(directory, src, ModuleLayout::ModRs),
+            new_module_file(directory, src, ModuleLayout::NonModRs),
+        ]
+    };
+
+    for candidate in candidates {
+        if path_exists(&candidate).await? {
+            return Ok(Some(candidate));
+        }
+    }
+
+    Ok(None)
+}
+
+fn new_module_file(directory: &Path, src: &Path, layout: ModuleLayout) -> PathBuf {
+    let non_mod_rs = match (directory.parent(), directory.file_name()) {
+        // `src/lib.rs` and `src/main.rs` are crate roots, so `src/lib/` needs a `mod.rs`.
+        (Some(parent), Some(name)) if !(parent == src && (name == "lib" || name == "main")) => {
+            Some(parent.join(name).with_extension("rs"))
+        }
+        _ => None,
+    };
+
+    match (layout, non_mod_rs) {
+        (ModuleLayout::NonModRs, Some(path)) => path,
+        _ => directory.join("mod.rs"),
+    }
+}
+
+/// Insert `pub mod {name};` among the existing module declarations in sorted order.
+///
+/// Returns `None` if the module is already declared.
+fn insert_module_declaration(content: &str, name: &str) -> Option<String> {
+    let mut lines = content.split_inclusive('\n').collect::<Vec<_>>();
+    if lines.iter().any(|line| declared_module(line) == Some(name)) {
+        return None;
+    }
+
+    let declaration = format!("pub mod {name};\n");
+    let declarations = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| Some((index, declared_module(line)?)))
+        .collect::<Vec<_>>();
+
+    let mut insert = vec![declaration.as_str()];
+    let index = if let Some((next, _)) = declarations.iter().find(|(_, declared)| *declared > name)
+    {
+        // Insert before the next declaration and its attributes.
+        let mut index = *next;
+        while index > 0 && is_outer_attribute_or_doc(lines[index - 1]) {
+            index -= 1;
+        }
+        index
+    } else if let Some((last, _)) = declarations.last() {
+        last + 1
+    } else {
+        // Insert after inner attributes and docs, separated from other items.
+        let index = lines
+            .iter()
+            .take_while(|line| {
+                let line = line.trim_start();
+                line.starts_with("//!") || line.starts_with("#![")
+            })
+            .count();
+        if index > 0 {
+            insert.insert(0, "\n");
+        }
+        if lines.get(index).is_some_and(|line| !line.trim().is_empty()) {
+            insert.push("\n");
+        }
+        index
+    };
+
+    // Ensure the previous line ends before the declaration.
+    if index > 0 && !lines[index - 1].ends_with('\n') {
+        insert.insert(0, "\n");
+    }
+    lines.splice(index..index, insert);
+
+    Some(lines.concat())
+}
+
+/// Remove the declarations of a module with their attributes and docs, so they don't apply to the next item.
+///
+/// Returns `None` if the module is not declared.
+fn remove_declaration(content: &str, name: &str) -> Option<String> {
+    let mut lines = content.split_inclusive('\n').collect::<Vec<_>>();
+    let declarations = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| declared_module(line) == Some(name))
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    if declarations.is_empty() {
+        return None;
+    }
+
+    for end in declarations.into_iter().rev() {
+        let mut start = end;
+        while start > 0 && is_outer_attribute_or_doc(lines[start - 1]) {
+            start -= 1;
+        }
+        lines.drain(start..=end);
+    }
+
+    Some(lines.concat())
+}
+
+fn is_outer_attribute_or_doc(line: &str) -> bool {
+    let line = line.trim_start();
+
+    (line.starts_with("#[") || line.starts_with("///")) && !line.starts_with("#![")
+}
+
+/// Get the module declared by a line, e.g. `button` for `pub mod button;`.
+fn declared_module(line: &str) -> Option<&str> {
+    let line = line.trim();
+    let line = match line.strip_prefix("pub") {
+        Some(rest) if rest.starts_with('(') => &rest[rest.find(')')? + 1..],
+        Some(rest) if rest.starts_with(char::is_whitespace) => rest,
+        _ => line,
+    };
+
+    let name = line
+        .trim_start()
+        .strip_prefix("mod")
+        .filter(|rest| rest.starts_with(char::is_whitespace))?
+        .trim()
+        .strip_suffix(';')?
+        .trim();
+
+    Some(name.strip_prefix("r#").unwrap_or(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::{TempDir, config};
+
+    #[tokio::test]
+    async fn declares_modules_with_mod_rs() {
+        let dir = TempDir::new();
+        dir.write("src/lib.rs", "//! App.\n\npub mod app;\n");
+        dir.write("src/components/ui/button.rs", "");
+        dir.write("src/common/utils.rs", "");
+        let config = config(dir.path()).await;
+
+        let changes = add_module_declarations(
+            &[
+                config.resolved_paths.ui.join("button.rs"),
+                config.resolved_paths.lib.join("utils.rs"),
+            ],
+            &config,
+            ModuleLayout::ModRs,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            changes.created,
+            [
+                PathBuf::from("src/components/ui/mod.rs"),
+                PathBuf::from("src/components/mod.rs"),
+                PathBuf::from("src/common/mod.rs"),
+            ]
+        );
+        assert_eq!(changes.updated, [PathBuf::from("src/lib.rs")]);
+        assert_eq!(dir.read("src/components/ui/mod.rs"), "pub mod button;\n");
+        assert_eq!(dir.read("src/components/mod.rs"), "pub mod ui;\n");
+        assert_eq!(dir.read("src/common/mod.rs"), "pub mod utils;\n");
+        assert_eq!(
+            dir.read("src/lib.rs"),
+            "//! App.\n\npub mod app;\npub mod common;\npub mod components;\n"
+        );
+        assert_eq!(
+            get_module_layout(&config).await.unwrap(),
+            ModuleLayout::ModRs
+        );
+    }
+
+    #[tokio::test]
+    async fn declares_modules_without_mod_rs() {
+        let dir = TempDir::new();
+        dir.write("src/main.rs", "fn main() {}\n");
+        dir.write("src/components/ui/button.rs", "");
+        dir.write("src/lib/utils.rs", "");
+        let config = config(dir.path()).await;
+
+        let changes = add_module_declarations(
+            &[
+                config.resolved_paths.ui.join("button.rs"),
+                config.resolved_paths.lib.join("utils.rs"),
+            ],
+            &config,
+            ModuleLayout::NonModRs,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            changes.created,
+            [
+                PathBuf::from("src/components/ui.rs"),
+                PathBuf::from("src/components.rs"),
+                // `src/lib.rs` would be a crate root.
+                PathBuf::from("src/lib/mod.rs"),
+            ]
+        );
+        assert_eq!(dir.read("src/components/ui.rs"), "pub mod button;\n");
+        assert_eq!(dir.read("src/components.rs"), "pub mod ui;\n");
+        assert_eq!(dir.read("src/lib/mod.rs"), "pub mod utils;\n");
+        assert_eq!(
+            dir.read("src/main.rs"),
+            "pub mod components;\npub mod lib;\n\nfn main() {}\n"
+        );
+        assert_eq!(
+            get_module_layout(&config).await.unwrap(),
+            ModuleLayout::NonModRs
+        );
+    }
+
+    #[tokio::test]
+    async fn removes_module_declaration() {
+        let dir = TempDir::new();
+        dir.write("src/main.rs", "fn main() {}\n");
+        dir.write(
+            "src/components/ui.rs",
+            "pub mod badge;\n#[cfg(feature = \"button\")]\npub mod button;\npub mod card;\n",
+        );
+        let config = config(dir.path()).await;
+
+        let module_file =
+            remove_module_declaration(&config.resolved_paths.ui.join("button.rs"), &config)
+                .await
+                .unwrap();
+
+        assert_eq!(module_file, Some(dir.path().join("src/components/ui.rs")));
+        assert_eq!(
+            dir.read("src/components/ui.rs"),
+            "pub mod badge;\npub mod card;\n"
+        );
+        assert_eq!(
+            remove_module_declaration(&config.resolved_paths.ui.join("input.rs"), &config)
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn inserts_declarations_in_order() {
+        assert_eq!(
+            insert_module_declaration("", "button").unwrap(),
+            "pub mod button;\n"
+        );
+        assert_eq!(
+            insert_module_declaration("pub mod badge;\n/// Cards.\npub mod card;\n", "button")
+                .unwrap(),
+            "pub mod badge;\npub mod button;\n/// Cards.\npub mod card;\n"
+        );
+        assert_eq!(
+            insert_module_declaration("#![allow(unused)]\nuse std::fs;\n", "button").unwrap(),
+            "#![allow(unused)]\n\npub mod button;\n\nuse std::fs;\n"
+        );
+        assert_eq!(
+            insert_module_declaration("pub(crate) mod button;", "button"),
+            None
+        );
+    }
+
+    #[test]
+    fn removes_declarations_with_attributes() {
+        assert_eq!(
+            remove_declaration(
+                "pub mod badge;\n/// Buttons.\n#[cfg(feature = \"button\")]\n#[path = \"button/mod.rs\"]\npub mod button;\npub mod card;\n",
+                "button"
+            )
+            .unwrap(),
+            "pub mod badge;\npub mod card;\n"
+        );
+        assert_eq!(
+            remove_declaration("#![allow(unused)]\npub mod button;\n", "button").unwrap(),
+            "#![allow(unused)]\n"
+        );
+        assert_eq!(remove_declaration("pub mod badge;\n", "button"), None);
+    }
+
+    #[test]
+    fn parses_declarations() {
+        assert_eq!(declared_module("pub mod button;"), Some("button"));
+        assert_eq!(declared_module("  pub(crate) mod r#type ;"), Some("type"));
+        assert_eq!(declared_module("mod button {"), None);
+        assert_eq!(declared_module("// mod button;"), None);
+    }
+}
@@ -0,0 +1,514 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    io,
+    path::{self, Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+};
+
+use anyhow::Result;
+use serde::Serialize;
+use similar::TextDiff;
+use tokio::fs;
+
+use crate::utils::{highlighter::HIGHLIGHTER, logger::LOGGER};
+
+/// Changes which are recorded instead of written during a dry run.
+struct DryRun {
+    cwd: PathBuf,
+    /// Changed paths, in the order they were first changed.
+    paths: Vec<PathBuf>,
+    /// Content before the dry run, `None` if the file did not exist.
+    original: HashMap<PathBuf, Option<String>>,
+    /// Content after the dry run, `None` if the file was removed.
+    current: HashMap<PathBuf, Option<String>>,
+    dependencies_added: Vec<String>,
+    dependencies_removed: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct Plan {
+    pub files: Vec<PlannedFile>,
+    pub dependencies: PlannedDependencies,
+}
+
+#[derive(Serialize)]
+pub struct PlannedFile {
+    pub path: String,
+    pub action: FileAction,
+    /// Unified diff of the change.
+    pub diff: String,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileAction {
+    Create,
+    Modify,
+    Delete,
+}
+
+#[derive(Serialize)]
+pub struct PlannedDependencies {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+#[cfg(not(test))]
+fn dry_run() -> MutexGuard<'static, Option<DryRun>> {
+    static DRY_RUN: Mutex<Option<DryRun>> = Mutex::new(None);
+
+    DRY_RUN
+        .lock()
+        .expect("Dry run lock should not be poisoned.")
+}
+
+/// Tests run in parallel, so each test thread has its own dry run.
+#[cfg(test)]
+fn dry_run() -> MutexGuard<'static, Option<DryRun>> {
+    thread_local! {
+        static DRY_RUN: &'static Mutex<Option<DryRun>> = Box::leak(Box::default());
+    }
+
+    DRY_RUN
+        .with(|dry_run| *dry_run)
+        .lock()
+        .expect("Dry run lock should not be poisoned.")
+}
+
+/// Record changes instead of writing them until the plan is printed. Paths in the plan are relative to `cwd`.
+///
+/// Logs are written to stderr if the plan is printed as JSON.
+pub fn start_dry_run(cwd: &Path, json: bool) {
+    if json {
+        LOGGER.use_stderr();
+    }
+
+    *dry_run() = Some(DryRun {
+        cwd: cwd.canonicalize().unwrap_or_else(|_| normalize(cwd)),
+        paths: vec![],
+        original: HashMap::new(),
+        current: HashMap::new(),
+        dependencies_added: vec![],
+        dependencies_removed: vec![],
+    });
+}
+
+pub fn is_dry_run() -> bool {
+    dry_run().is_some()
+}
+
+/// Make a path absolute with a canonical parent, so the same file is recorded once however it is referred to.
+fn normalize(path: &Path) -> PathBuf {
+    let path = path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+
+    match (
+        path.parent().and_then(|parent| parent.canonicalize().ok()),
+        path.file_name(),
+    ) {
+        (Some(parent), Some(name)) => parent.join(name),
+        _ => path,
+    }
+}
+
+/// Canonicalize a path, which may be a directory of files created during a dry run.
+pub async fn canonicalize(path: &Path) -> Result<PathBuf> {
+    if fs::try_exists(path).await? || !path_exists(path).await? {
+        return Ok(fs::canonicalize(path).await?);
+    }
+
+    Ok(normalize(path))
+}
+
+/// An entry of a directory listed by [`read_dir`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DirEntry {
+    pub name: String,
+    pub is_dir: bool,
+}
+
+/// List a directory sorted by name, including changes recorded during a dry run.
+pub async fn read_dir(path: &Path) -> Result<Vec<DirEntry>> {
+    let mut entries = BTreeMap::new();
+    if fs::try_exists(path).await? {
+        let mut dir = fs::read_dir(path).await?;
+        while let Some(entry) = dir.next_entry().await? {
+            entries.insert(
+                entry.file_name().to_string_lossy().into_owned(),
+                entry.file_type().await?.is_dir(),
+            );
+        }
+    }
+
+    let directory = path.canonicalize().unwrap_or_else(|_| normalize(path));
+    if let Some(dry_run) = dry_run().as_ref() {
+        for (file, content) in &dry_run.current {
+            let Ok(relative_path) = file.strip_prefix(&directory) else {
+                continue;
+            };
+            let mut components = relative_path.components();
+            let Some(name) = components.next() else {
+                continue;
+            };
+            let name = name.as_os_str().to_string_lossy().into_owned();
+            let is_dir = components.next().is_some();
+
+            match content {
+                Some(_) if is_dir => {
+                    entries.entry(name).or_insert(true);
+                }
+                Some(_) => {
+                    entries.insert(name, false);
+                }
+                None if !is_dir => {
+                    entries.remove(&name);
+                }
+                None => {}
+            }
+        }
+    }
+
+    Ok(entries
+        .into_iter()
+        .map(|(name, is_dir)| DirEntry { name, is_dir })
+        .collect())
+}
+
+/// The content of a file changed during the dry run, `Some(None)` if it was removed.
+fn planned_content(path: &Path) -> Option<Option<String>> {
+    dry_run()
+        .as_ref()
+        .and_then(|dry_run| dry_run.current.get(&normalize(path)).cloned())
+}
+
+/// Whether a file or directory exists, including changes recorded during a dry run.
+pub async fn path_exists(path: &Path) -> Result<bool> {
+    if let Some(content) = planned_content(path) {
+        return Ok(content.is_some());
+    }
+
+    // Directories of planned files exist once the plan is applied.
+    let path = normalize(path);
+    let planned_directory = dry_run().as_ref().is_some_and(|dry_run| {
+        dry_run
+            .current
+            .iter()
+            .any(|(file, content)| content.is_some() && file.starts_with(&path))
+    });
+
+    Ok(planned_directory || fs::try_exists(path).await?)
+}
+
+/// Read a file, including changes recorded during a dry run.
+pub async fn read_file(path: &Path) -> Result<String> {
+    match planned_content(path) {
+        Some(Some(content)) => Ok(content),
+        Some(None) => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} was removed.", path.display()),
+        )
+        .into()),
+        None => Ok(fs::read_to_string(path).await?),
+    }
+}
+
+/// Write a file and create its parent directories, or record the change during a dry run.
+pub async fn write_file(path: &Path, content: impl Into<String>) -> Result<()> {
+    let content = content.into();
+    if is_dry_run() {
+        return record(path, Some(content)).await;
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    fs::write(path, content).await?;
+
+    Ok(())
+}
+
+/// Remove a file, or record the change during a dry run.
+pub async fn remove_file(path: &Path) -> Result<()> {
+    if is_dry_run() {
+        return record(path, None).await;
+    }
+
+    fs::remove_file(path).await?;
+
+    Ok(())
+}
+
+async fn record(path: &Path, content: Option<String>) -> Result<()> {
+    let recorded = planned_content(path).is_some();
+    let original = if !recorded && fs::try_exists(path).await? {
+        Some(fs::read_to_string(path).await?)
+    } else {
+        None
+    };
+
+    let path = normalize(path);
+    if let Some(dry_run) = dry_run().as_mut() {
+        if !dry_run.original.contains_key(&path) {
+            dry_run.paths.push(path.clone());
+            dry_run.original.insert(path.clone(), original);
+        }
+        dry_run.current.insert(path, content);
+    }
+
+    Ok(())
+}
+
+/// Record the crates added to or removed from `Cargo.toml` during a dry run.
+pub fn record_dependencies(added: &[String], removed: &[String]) {
+    if let Some(dry_run) = dry_run().as_mut() {
+        dry_run.dependencies_added.extend_from_slice(added);
+        dry_run.dependencies_removed.extend_from_slice(removed);
+    }
+}
+
+/// End the dry run and get the changes it recorded.
+pub fn take_plan() -> Option<Plan> {
+    let dry_run = dry_run().take()?;
+
+    let files = dry_run
+        .paths
+        .iter()
+        .filter_map(|path| {
+            let original = dry_run.original.get(path)?.as_deref();
+            let current = dry_run.current.get(path)?.as_deref();
+            let action = match (original, current) {
+                (None, Some(_)) => FileAction::Create,
+                (Some(original), Some(current)) if original != current => FileAction::Modify,
+                (Some(_), None) => FileAction::Delete,
+                _ => return None,
+            };
+
+            let path = path
+                .strip_prefix(&dry_run.cwd)
+                .unwrap_or(path)
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            let (old_header, new_header) = match action {
+                FileAction::Create => ("/dev/null".into(), format!("b/{path}")),
+                FileAction::Modify => (format!("a/{path}"), format!("b/{path}")),
+                FileAction::Delete => (format!("a/{path}"), "/dev/null".into()),
+            };
+            let diff =
+                TextDiff::from_lines(original.unwrap_or_default(), current.unwrap_or_default())
+                    .unified_diff()
+                    .context_radius(3)
+                    .header(&old_header, &new_header)
+                    .to_string();
+
+            Some(PlannedFile { path, action, diff })
+        })
+        .collect();
+
+    Some(Plan {
+        files,
+        dependencies: PlannedDependencies {
+            added: dry_run.dependencies_added,
+            removed: dry_run.dependencies_removed,
+        },
+    })
+}
+
+/// End the dry run and print its changes, as JSON or with diffs.
+pub fn print_plan(json: bool) -> Result<()> {
+    let Some(plan) = take_plan() else {
+        return Ok(());
+    };
+
+    if json {
+        // Logs are written to stderr for JSON output, so stdout only contains the plan.
+        println!("{}", serde_json::to_string_pretty(&plan)?);
+        return Ok(());
+    }
+
+    LOGGER.r#break();
+    LOGGER.info("Dry run. No changes were written.");
+
+    if plan.files.is_empty()
+        && plan.dependencies.added.is_empty()
+        && plan.dependencies.removed.is_empty()
+    {
+        LOGGER.log("Nothing to change.");
+        return Ok(());
+    }
+
+    for file in &plan.files {
+        LOGGER.r#break();
+        LOGGER.log(&match file.action {
+            FileAction::Create => format!("{} {}", HIGHLIGHTER.success("Create"), file.path),
+            FileAction::Modify => format!("{} {}", HIGHLIGHTER.warn("Modify"), file.path),
+            FileAction::Delete => format!("{} {}", HIGHLIGHTER.error("Delete"), file.path),
+        });
+
+        // Skip the `---` and `+++` header lines.
+        for line in file.diff.lines().skip(2) {
+            if line.starts_with("@@") {
+                LOGGER.log(&HIGHLIGHTER.info(line));
+            } else if line.starts_with('+') {
+                LOGGER.log(&HIGHLIGHTER.success(line));
+            } else if line.starts_with('-') {
+                LOGGER.log(&HIGHLIGHTER.error(line));
+            } else {
+                LOGGER.log(line);
+            }
+        }
+    }
+
+    for (label, dependencies) in [
+        ("Add", &plan.dependencies.added),
+        ("Remove", &plan.dependencies.removed),
+    ] {
+        if dependencies.is_empty() {
+            continue;
+        }
+
+        LOGGER.r#break();
+        LOGGER.log(&format!(
+            "{} {} {}:",
+            label,
+            dependencies.len(),
+            if dependencies.len() == 1 {
+                "crate"
+            } else {
+                "crates"
+            }
+        ));
+        for dependency in dependencies {
+            LOGGER.log(&format!("  - {dependency}"));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::TempDir;
+
+    #[tokio::test]
+    async fn records_changes_without_writing() {
+        let dir = TempDir::new();
+        dir.write("modified.txt", "old\n");
+        dir.write("removed.txt", "removed\n");
+        start_dry_run(dir.path(), false);
+
+        let created = dir.path().join("new/created.txt");
+        write_file(&created, "created\n").await.unwrap();
+        write_file(&dir.path().join("modified.txt"), "new\n")
+            .await
+            .unwrap();
+        remove_file(&dir.path().join("removed.txt")).await.unwrap();
+
+        assert!(!created.exists());
+        assert!(!dir.path().join("new").exists());
+        assert_eq!(dir.read("modified.txt"), "old\n");
+        assert_eq!(dir.read("removed.txt"), "removed\n");
+
+        assert!(path_exists(&created).await.unwrap());
+        assert!(path_exists(&dir.path().join("new")).await.unwrap());
+        assert!(!path_exists(&dir.path().join("removed.txt")).await.unwrap());
+        assert_eq!(read_file(&created).await.unwrap(), "created\n");
+        assert_eq!(
+            read_file(&dir.path().join("modified.txt")).await.unwrap(),
+            "new\n"
+        );
+        assert!(read_file(&dir.path().join("removed.txt")).await.is_err());
+        assert_eq!(
+            canonicalize(&dir.path().join("new")).await.unwrap(),
+            dir.path().join("new")
+        );
+        assert_eq!(
+            read_dir(dir.path()).await.unwrap(),
+            [
+                DirEntry {
+                    name: "modified.txt".into(),
+                    is_dir: false
+                },
+                DirEntry {
+                    name: "new".into(),
+                    is_dir: true
+                },
+            ]
+        );
+
+        let plan = take_plan().unwrap();
+        assert!(!is_dry_run());
+        assert_eq!(
+            plan.files
+                .iter()
+                .map(|file| (file.path.as_str(), file.action))
+                .collect::<Vec<_>>(),
+            [
+                ("new/created.txt", FileAction::Create),
+                ("modified.txt", FileAction::Modify),
+                ("removed.txt", FileAction::Delete),
+            ]
+        );
+        assert_eq!(
+            plan.files[1].diff,
+            "--- a/modified.txt\n+++ b/modified.txt\n@@ -1 +1 @@\n-old\n+new\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn omits_changes_which_cancel_out() {
+        let dir = TempDir::new();
+        dir.write("unchanged.txt", "unchanged\n");
+        start_dry_run(dir.path(), false);
+
+        let unchanged = dir.path().join("unchanged.txt");
+        write_file(&unchanged, "changed\n").await.unwrap();
+        write_file(&unchanged, "unchanged\n").await.unwrap();
+        let temporary = dir.path().join("temporary.txt");
+        write_file(&temporary, "temporary\n").await.unwrap();
+        remove_file(&temporary).await.unwrap();
+
+        assert!(take_plan().unwrap().files.is_empty());
+    }
+
+    #[tokio::test]
+    async fn serializes_plan() {
+        let dir = TempDir::new();
+        start_dry_run(dir.path(), false);
+
+        write_file(&dir.path().join("created.txt"), "created\n")
+            .await
+            .unwrap();
+        record_dependencies(&["tailwind_fuse".into()], &["lucide-leptos".into()]);
+
+        let plan = serde_json::to_value(take_plan().unwrap()).unwrap();
+        assert_eq!(
+            plan,
+            serde_json::json!({
+                "files": [{
+                    "path": "created.txt",
+                    "action": "create",
+                    "diff": "--- /dev/null\n+++ b/created.txt\n@@ -0,0 +1 @@\n+created\n",
+                }],
+                "dependencies": {
+                    "added": ["tailwind_fuse"],
+                    "removed": ["lucide-leptos"],
+                },
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn writes_without_dry_run() {
+        let dir = TempDir::new();
+        assert!(take_plan().is_none());
+
+        let path = dir.path().join("new/created.txt");
+        write_file(&path, "created\n").await.unwrap();
+        assert_eq!(dir.read("new/created.txt"), "created\n");
+
+        remove_file(&path).await.unwrap();
+        assert!(!path_exists(&path).await.unwrap());
+    }
+}
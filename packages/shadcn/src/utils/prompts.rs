@@ -0,0 +1,34 @@
+use std::io::{self, IsTerminal};
+
+use anyhow::Result;
+use dialoguer::{Confirm, MultiSelect, Select, theme::ColorfulTheme};
+
+/// Whether prompts can be shown. Prompts are skipped when stdin is not a TTY, e.g. in CI.
+pub fn is_interactive() -> bool {
+    io::stdin().is_terminal() && io::stderr().is_terminal()
+}
+
+/// Ask a yes/no question.
+pub fn prompt_confirm(message: &str, default: bool) -> Result<bool> {
+    Ok(Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(message)
+        .default(default)
+        .interact()?)
+}
+
+/// Select one of `items`, returning its index.
+pub fn prompt_select<T: ToString>(message: &str, items: &[T], default: usize) -> Result<usize> {
+    Ok(Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(message)
+        .items(items.iter().map(ToString::to_string))
+        .default(default)
+        .interact()?)
+}
+
+/// Select any number of `items`, returning their indices.
+pub fn prompt_multi_select<T: ToString>(message: &str, items: &[T]) -> Result<Vec<usize>> {
+    Ok(MultiSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt(message)
+        .items(items.iter().map(ToString::to_string))
+        .interact()?)
+}
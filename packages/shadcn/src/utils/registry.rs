@@ -0,0 +1,584 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    env,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::{LazyLock, RwLock},
+};
+
+use anyhow::{Context, Result, anyhow};
+use regex::{Captures, Regex};
+use reqwest::{Client, StatusCode};
+use serde::de::DeserializeOwned;
+use shadcn_registry::{
+    registry_resolver::{ResolveError, resolve_registry_items_with},
+    schema::{
+        FrameworkName, Registry, RegistryBaseColor, RegistryEntry, RegistryItemCssVars,
+        RegistryItemDependency, RegistryItemFile, RegistryItemType, Style,
+    },
+};
+use tokio::fs;
+
+use crate::utils::{errors::ErrorType, get_config::Config, highlighter::HIGHLIGHTER};
+
+pub const DEFAULT_REGISTRY_URL: &str = "https://shadcn-ui.rustforweb.org/r";
+
+/// Where registry payloads are read from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RegistrySource {
+    /// A static registry served over HTTP, e.g. `https://shadcn-ui.rustforweb.org/r`.
+    Url(String),
+    /// A static registry written by `build_registry`, e.g. `file://./dist`, which works offline.
+    Directory(PathBuf),
+}
+
+impl RegistrySource {
+    /// Parse a registry source from an HTTP(S) URL or a `file://` URL, resolving relative directories against `cwd`.
+    pub fn parse(value: &str, cwd: &Path) -> Self {
+        let value = value.trim_end_matches('/');
+
+        if let Some(path) = value.strip_prefix("file://") {
+            let path = cwd.join(path);
+
+            // Accept both the output directory of `build_registry` and its `r` subdirectory.
+            if path.join("r").is_dir() && !path.join("frameworks").is_dir() {
+                RegistrySource::Directory(path.join("r"))
+            } else {
+                RegistrySource::Directory(path)
+            }
+        } else {
+            RegistrySource::Url(value.into())
+        }
+    }
+}
+
+/// The registry configuration of the project a command runs in.
+struct RegistryContext {
+    /// The registry URL, configured with the `REGISTRY_URL` environment variable.
+    url: String,
+    /// The project root, which relative registry directories are resolved against.
+    cwd: PathBuf,
+    headers: HashMap<String, String>,
+}
+
+impl Default for RegistryContext {
+    fn default() -> Self {
+        RegistryContext {
+            url: env::var("REGISTRY_URL").unwrap_or_else(|_| DEFAULT_REGISTRY_URL.into()),
+            cwd: PathBuf::new(),
+            headers: HashMap::new(),
+        }
+    }
+}
+
+#[cfg(not(test))]
+fn registry_context() -> &'static RwLock<RegistryContext> {
+    static REGISTRY_CONTEXT: LazyLock<RwLock<RegistryContext>> = LazyLock::new(RwLock::default);
+
+    &REGISTRY_CONTEXT
+}
+
+/// Tests run in parallel, so each test thread has its own registry configuration.
+#[cfg(test)]
+fn registry_context() -> &'static RwLock<RegistryContext> {
+    thread_local! {
+        static REGISTRY_CONTEXT: &'static RwLock<RegistryContext> = Box::leak(Box::default());
+    }
+
+    REGISTRY_CONTEXT.with(|context| *context)
+}
+
+/// Use another registry in tests, e.g. `file://registry` relative to the project root.
+#[cfg(test)]
+pub fn set_registry_url(url: &str) {
+    registry_context()
+        .write()
+        .expect("Registry context lock should not be poisoned.")
+        .url = url.into();
+}
+
+/// The registry source of the project.
+pub fn registry_source() -> RegistrySource {
+    let context = registry_context()
+        .read()
+        .expect("Registry context lock should not be poisoned.");
+
+    RegistrySource::parse(&context.url, &context.cwd)
+}
+
+static CLIENT: LazyLock<Client> = LazyLock::new(Client::new);
+
+/// Configure the registry for the project: relative registry directories are resolved against its root, and headers
+/// are set from `components.toml` and the `REGISTRY_TOKEN` environment variable.
+pub fn set_registry_config(config: &Config) {
+    let env_var_regex = Regex::new(r"\$\{(\w+)\}").expect("Regex should be valid.");
+
+    let mut headers = HashMap::new();
+    if let Ok(token) = env::var("REGISTRY_TOKEN") {
+        headers.insert("Authorization".into(), format!("Bearer {token}"));
+    }
+    for (name, value) in &config.registry.headers {
+        let value = env_var_regex.replace_all(value, |captures: &Captures| {
+            env::var(&captures[1]).unwrap_or_default()
+        });
+        if !value.trim().is_empty() {
+            headers.insert(name.clone(), value.to_string());
+        }
+    }
+
+    let mut context = registry_context()
+        .write()
+        .expect("Registry context lock should not be poisoned.");
+    context.cwd = config.resolved_paths.cwd.clone();
+    context.headers = headers;
+}
+
+/// Get the items of the registry for a framework.
+pub async fn get_registry_index(framework: FrameworkName) -> Result<Registry> {
+    fetch_registry(&format!("frameworks/{framework}/index.json")).await
+}
+
+pub async fn get_registry_item(
+    name: &str,
+    framework: FrameworkName,
+    style: Style,
+) -> Result<RegistryEntry> {
+    if is_url(name) {
+        return fetch_registry(name).await;
+    }
+
+    fetch_registry(&format!(
+        "frameworks/{framework}/styles/{style}/{name}.json"
+    ))
+    .await
+}
+
+/// Get the location an item is fetched from, to record where it was installed from.
+pub fn get_registry_item_url(name: &str, framework: FrameworkName, style: Style) -> String {
+    if is_url(name) {
+        return name.into();
+    }
+
+    let path = format!("frameworks/{framework}/styles/{style}/{name}.json");
+    match registry_source() {
+        RegistrySource::Url(url) => format!("{url}/{path}"),
+        RegistrySource::Directory(directory) => {
+            format!("file://{}", directory.join(path).display())
+        }
+    }
+}
+
+pub async fn get_registry_base_color(base_color: &str) -> Result<RegistryBaseColor> {
+    fetch_registry(&format!("colors/{base_color}.json")).await
+}
+
+pub struct RegistryItemsTree {
+    pub dependencies: Vec<RegistryItemDependency>,
+    pub dev_dependencies: Vec<RegistryItemDependency>,
+    pub files: Vec<RegistryItemFile>,
+    pub css_vars: RegistryItemCssVars,
+    pub docs: Option<String>,
+}
+
+/// Fetch the items and their registry dependencies, with dependencies ordered before their dependents.
+pub async fn registry_resolve_items(
+    names: &[String],
+    framework: FrameworkName,
+    style: Style,
+) -> Result<Vec<RegistryEntry>> {
+    let mut fetched: HashMap<String, RegistryEntry> = HashMap::new();
+    let mut missing: HashMap<String, anyhow::Error> = HashMap::new();
+    let mut queue = names.iter().cloned().collect::<VecDeque<_>>();
+
+    while let Some(name) = queue.pop_front() {
+        if fetched.contains_key(&name) || missing.contains_key(&name) {
+            continue;
+        }
+
+        match get_registry_item(&name, framework, style).await {
+            Ok(item) => {
+                queue.extend(item.registry_dependencies.iter().flatten().cloned());
+                fetched.insert(name, item);
+            }
+            Err(error) if error.downcast_ref() == Some(&ErrorType::ComponentUrlNotFound) => {
+                missing.insert(name, error);
+            }
+            Err(error) => return Err(error),
+        }
+    }
+
+    match resolve_registry_items_with(names, framework, style, |name| fetched.get(name).cloned()) {
+        Ok(plan) => Ok(plan.items),
+        Err(ResolveError::MissingItem { name, path }) if path.len() == 1 => Err(missing
+            .remove(&name)
+            .unwrap_or_else(|| anyhow!(ErrorType::ComponentUrlNotFound))),
+        Err(error @ ResolveError::MissingItem { .. }) => {
+            Err(anyhow!(ErrorType::ComponentUrlNotFound).context(error.to_string()))
+        }
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Merge the dependencies, files, CSS variables and docs of resolved items.
+pub fn registry_items_tree(items: Vec<RegistryEntry>) -> RegistryItemsTree {
+    let mut tree = RegistryItemsTree {
+        dependencies: vec![],
+        dev_dependencies: vec![],
+        files: vec![],
+        css_vars: RegistryItemCssVars::new(),
+        docs: None,
+    };
+
+    for item in items {
+        for dependency in item.dependencies.into_iter().flatten() {
+            if !tree.dependencies.contains(&dependency) {
+                tree.dependencies.push(dependency);
+            }
+        }
+        for dependency in item.dev_dependencies.into_iter().flatten() {
+            if !tree.dev_dependencies.contains(&dependency) {
+                tree.dev_dependencies.push(dependency);
+            }
+        }
+
+        tree.files.extend(item.files.into_iter().flatten());
+
+        for (mode, vars) in item.css_vars.into_iter().flatten() {
+            tree.css_vars.entry(mode).or_default().extend(vars);
+        }
+
+        if let Some(docs) = item.docs {
+            tree.docs = Some(match tree.docs {
+                Some(existing) => format!("{existing}\n{docs}"),
+                None => docs,
+            });
+        }
+    }
+
+    tree
+}
+
+pub fn get_registry_item_file_path(
+    file: &RegistryItemFile,
+    config: &Config,
+    r#override: Option<&Path>,
+) -> PathBuf {
+    if let Some(target) = &file.target {
+        return config.resolved_paths.cwd.join(target);
+    }
+
+    let target_dir = get_registry_item_file_target_path(file, config, r#override);
+    let file_name = Path::new(&file.path)
+        .file_name()
+        .expect("Registry file path should have a file name.");
+
+    target_dir.join(file_name)
+}
+
+pub fn get_registry_item_file_target_path(
+    file: &RegistryItemFile,
+    config: &Config,
+    r#override: Option<&Path>,
+) -> PathBuf {
+    if let Some(r#override) = r#override {
+        return config.resolved_paths.cwd.join(r#override);
+    }
+
+    match file.r#type {
+        RegistryItemType::Ui => config.resolved_paths.ui.clone(),
+        RegistryItemType::Lib => config.resolved_paths.lib.clone(),
+        RegistryItemType::Hook => config.resolved_paths.hooks.clone(),
+        // TODO: We put this in components for now. We should move this to pages as per framework.
+        _ => config.resolved_paths.components.clone(),
+    }
+}
+
+async fn fetch_registry<T: DeserializeOwned>(path: &str) -> Result<T> {
+    if is_url(path) {
+        return fetch_url(path).await;
+    }
+
+    match registry_source() {
+        RegistrySource::Url(url) => fetch_url(&format!("{url}/{path}")).await,
+        RegistrySource::Directory(directory) => {
+            let path = directory.join(path);
+            let content = match fs::read_to_string(&path).await {
+                Ok(content) => content,
+                Err(error) if error.kind() == ErrorKind::NotFound => {
+                    return Err(anyhow!(ErrorType::ComponentUrlNotFound).context(format!(
+                        "The component at {} was not found.\nIt may not exist in the registry. Please make sure it is a valid component.",
+                        HIGHLIGHTER.info(&path.to_string_lossy())
+                    )));
+                }
+                Err(error) => {
+                    return Err(error)
+                        .with_context(|| format!("Failed to read {}.", path.display()));
+                }
+            };
+
+            serde_json::from_str(&content)
+                .with_context(|| format!("Invalid registry payload in {}.", path.display()))
+        }
+    }
+}
+
+async fn fetch_url<T: DeserializeOwned>(url: &str) -> Result<T> {
+    let headers = match registry_source() {
+        RegistrySource::Url(registry_url) => get_url_headers(
+            url,
+            &registry_url,
+            &registry_context()
+                .read()
+                .expect("Registry context lock should not be poisoned.")
+                .headers,
+        ),
+        RegistrySource::Directory(_) => HashMap::new(),
+    };
+
+    fetch_url_with_headers(url, &headers).await
+}
+
+/// Get the headers to send with a request, which are only sent to the configured registry.
+fn get_url_headers(
+    url: &str,
+    registry_url: &str,
+    headers: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let is_registry_url = url
+        .strip_prefix(registry_url.trim_end_matches('/'))
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'));
+
+    if is_registry_url {
+        headers.clone()
+    } else {
+        HashMap::new()
+    }
+}
+
+async fn fetch_url_with_headers<T: DeserializeOwned>(
+    url: &str,
+    headers: &HashMap<String, String>,
+) -> Result<T> {
+    let mut request = CLIENT.get(url);
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+
+    let response = request
+        .send()
+        .await
+        .with_context(|| format!("Failed to fetch from {url}."))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let url = HIGHLIGHTER.info(url);
+        let body = response.text().await.unwrap_or_default();
+
+        return Err(match status {
+            StatusCode::BAD_REQUEST => anyhow!(ErrorType::ComponentUrlBadRequest).context(format!(
+                "The registry rejected the request for the component at {url}.\n{}",
+                error_detail(&body, status)
+            )),
+            StatusCode::UNAUTHORIZED => {
+                anyhow!(ErrorType::ComponentUrlUnauthorized).context(format!(
+                    "You are not authorized to access the component at {url}.\nIf this is a private registry, set {} or add an {} header to the {} section of {}.",
+                    HIGHLIGHTER.info("REGISTRY_TOKEN"),
+                    HIGHLIGHTER.info("Authorization"),
+                    HIGHLIGHTER.info("[registry.headers]"),
+                    HIGHLIGHTER.info("components.toml"),
+                ))
+            }
+            StatusCode::FORBIDDEN => anyhow!(ErrorType::ComponentUrlForbidden).context(format!(
+                "You do not have access to the component at {url}.\nIf this is a private registry, check that your token grants access to it."
+            )),
+            StatusCode::NOT_FOUND => anyhow!(ErrorType::ComponentUrlNotFound).context(format!(
+                "The component at {url} was not found.\nIt may not exist in the registry. Please make sure it is a valid component."
+            )),
+            StatusCode::INTERNAL_SERVER_ERROR => {
+                anyhow!(ErrorType::ComponentUrlInternalServerError).context(format!(
+                    "The registry failed to serve the component at {url}.\n{}",
+                    error_detail(&body, status)
+                ))
+            }
+            _ => anyhow!(
+                "Failed to fetch from {url}.\n{}",
+                error_detail(&body, status)
+            ),
+        });
+    }
+
+    response
+        .json()
+        .await
+        .with_context(|| format!("Invalid registry payload from {url}."))
+}
+
+/// Extract an error message from a registry response, falling back to the status.
+fn error_detail(body: &str, status: StatusCode) -> String {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|value| {
+            ["error", "message"]
+                .into_iter()
+                .find_map(|key| value.get(key)?.as_str().map(String::from))
+        })
+        .unwrap_or_else(|| status.to_string())
+}
+
+fn is_url(path: &str) -> bool {
+    path.starts_with("http://") || path.starts_with("https://")
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        task::JoinHandle,
+    };
+
+    use super::*;
+    use crate::utils::test_utils::{TempDir, config};
+
+    /// Serve a single response on a local port and return the URL and the request it received.
+    async fn serve_once(status: u16, body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = vec![];
+            let mut buffer = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let length = stream.read(&mut buffer).await.unwrap();
+                if length == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buffer[..length]);
+            }
+
+            let response = format!(
+                "HTTP/1.1 {status} Status\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+
+            String::from_utf8(request).unwrap()
+        });
+
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn maps_status_to_error_type() {
+        for (status, error_type) in [
+            (400, ErrorType::ComponentUrlBadRequest),
+            (401, ErrorType::ComponentUrlUnauthorized),
+            (403, ErrorType::ComponentUrlForbidden),
+            (404, ErrorType::ComponentUrlNotFound),
+            (500, ErrorType::ComponentUrlInternalServerError),
+        ] {
+            let (url, handle) = serve_once(status, r#"{"error":"Token expired."}"#).await;
+
+            let error = fetch_url_with_headers::<serde_json::Value>(
+                &format!("{url}/index.json"),
+                &HashMap::new(),
+            )
+            .await
+            .unwrap_err();
+            handle.await.unwrap();
+
+            assert_eq!(error.downcast_ref::<ErrorType>(), Some(&error_type));
+        }
+    }
+
+    #[tokio::test]
+    async fn includes_error_detail() {
+        let (url, handle) = serve_once(503, r#"{"message":"Down for maintenance."}"#).await;
+
+        let error = fetch_url_with_headers::<serde_json::Value>(
+            &format!("{url}/index.json"),
+            &HashMap::new(),
+        )
+        .await
+        .unwrap_err();
+        handle.await.unwrap();
+
+        assert_eq!(error.downcast_ref::<ErrorType>(), None);
+        assert!(error.to_string().contains("Down for maintenance."));
+    }
+
+    #[tokio::test]
+    async fn sends_headers() {
+        let (url, handle) = serve_once(200, "[]").await;
+        let headers = HashMap::from([("Authorization".to_string(), "Bearer token".to_string())]);
+
+        let payload =
+            fetch_url_with_headers::<serde_json::Value>(&format!("{url}/index.json"), &headers)
+                .await
+                .unwrap();
+        let request = handle.await.unwrap().to_lowercase();
+
+        assert_eq!(payload, serde_json::json!([]));
+        assert!(request.contains("authorization: bearer token"));
+    }
+
+    #[test]
+    fn scopes_headers_to_registry() {
+        let registry_url = "https://registry.example.com/r";
+        let headers = HashMap::from([("Authorization".to_string(), "Bearer token".to_string())]);
+
+        for url in [
+            "https://registry.example.com/r/index.json",
+            "https://registry.example.com/r",
+        ] {
+            assert_eq!(get_url_headers(url, registry_url, &headers), headers);
+        }
+        for url in [
+            "https://registry.example.com/other/index.json",
+            "https://registry.example.com/rx/index.json",
+            "https://registry.example.com.evil.dev/r/index.json",
+            "https://example.com/r/index.json",
+        ] {
+            assert!(get_url_headers(url, registry_url, &headers).is_empty());
+        }
+    }
+
+    #[test]
+    fn resolves_registry_directory_against_project() {
+        let dir = TempDir::new();
+        dir.write("dist/r/frameworks/leptos/index.json", "[]");
+
+        assert_eq!(
+            RegistrySource::parse("file://./dist/", dir.path()),
+            RegistrySource::Directory(dir.path().join("./dist/r"))
+        );
+        assert_eq!(
+            RegistrySource::parse("file:///srv/registry", dir.path()),
+            RegistrySource::Directory("/srv/registry".into())
+        );
+        assert_eq!(
+            RegistrySource::parse("https://registry.example.com/r/", dir.path()),
+            RegistrySource::Url("https://registry.example.com/r".into())
+        );
+    }
+
+    #[tokio::test]
+    async fn fetches_from_registry_directory() {
+        let dir = TempDir::new();
+        dir.write(
+            "registry/r/colors/slate.json",
+            r#"{"inlineColors":{"light":{},"dark":{}},"cssVars":{"light":{},"dark":{}},"inlineColorsTemplate":"","cssVarsTemplate":""}"#,
+        );
+        set_registry_config(&config(dir.path()).await);
+        set_registry_url("file://registry");
+
+        assert!(get_registry_base_color("slate").await.is_ok());
+
+        let error = get_registry_base_color("zinc").await.unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<ErrorType>(),
+            Some(&ErrorType::ComponentUrlNotFound)
+        );
+    }
+}
@@ -1,6 +1,6 @@
-use spinners::{Spinner as InnerSpinner, Spinners};
+use spinners::{Spinner as InnerSpinner, Spinners, Stream};
 
-use crate::utils::highlighter::HIGHLIGHTER;
+use crate::utils::{highlighter::HIGHLIGHTER, logger::LOGGER};
 
 pub struct Spinner {
     inner: InnerSpinner,
@@ -10,7 +10,11 @@ pub struct Spinner {
 impl Spinner {
     fn new(text: String, options: SpinnerOptions) -> Self {
         Self {
-            inner: InnerSpinner::new(Spinners::Dots, text),
+            inner: if LOGGER.uses_stderr() {
+                InnerSpinner::with_stream(Spinners::Dots, text, Stream::Stderr)
+            } else {
+                InnerSpinner::new(Spinners::Dots, text)
+            },
             options,
         }
     }
@@ -20,8 +24,10 @@ impl Spinner {
     }
 
     pub fn succeed(&mut self, text: Option<String>) {
-        // TODO: text
-        self.inner.stop_with_symbol(&HIGHLIGHTER.success("✔"));
+        match text {
+            Some(text) => self.inner.stop_and_persist(&HIGHLIGHTER.success("✔"), text),
+            None => self.inner.stop_with_symbol(&HIGHLIGHTER.success("✔")),
+        }
     }
 }
 
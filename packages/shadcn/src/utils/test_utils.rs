@@ -0,0 +1,143 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::utils::{
+    get_config::{
+        CONFIG_FILE_NAME, Config, DEFAULT_STYLE, DEFAULT_TAILWIND_BASE_COLOR,
+        DEFAULT_TAILWIND_CONFIG, DEFAULT_TAILWIND_CSS, PathsConfig, RawConfig, RegistryConfig,
+        TailwindConfig, resolve_config_paths,
+    },
+    registry::{set_registry_config, set_registry_url},
+};
+
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A temporary directory which is removed when dropped.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new() -> Self {
+        let path = std::env::temp_dir().join(format!(
+            "shadcn-test-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&path).expect("Temporary directory should be created.");
+
+        TempDir(
+            path.canonicalize()
+                .expect("Temporary directory should exist."),
+        )
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+
+    /// Write a file relative to the directory, creating its parent directories.
+    pub fn write(&self, path: &str, content: &str) -> PathBuf {
+        let path = self.0.join(path);
+        std::fs::create_dir_all(path.parent().expect("Path should have a parent."))
+            .expect("Parent directory should be created.");
+        std::fs::write(&path, content).expect("File should be written.");
+
+        path
+    }
+
+    /// Read a file relative to the directory.
+    pub fn read(&self, path: &str) -> String {
+        std::fs::read_to_string(self.0.join(path)).expect("File should be read.")
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+pub fn raw_config(paths: PathsConfig) -> RawConfig {
+    RawConfig {
+        style: DEFAULT_STYLE,
+        tailwind: TailwindConfig {
+            config: DEFAULT_TAILWIND_CONFIG.into(),
+            css: DEFAULT_TAILWIND_CSS.into(),
+            base_color: DEFAULT_TAILWIND_BASE_COLOR.into(),
+            css_variables: true,
+            prefix: String::new(),
+        },
+        paths,
+        registry: RegistryConfig::default(),
+    }
+}
+
+/// Resolve the default configuration in a directory.
+pub async fn config(cwd: &Path) -> Config {
+    resolve_config_paths(cwd, raw_config(PathsConfig::default()))
+        .await
+        .expect("Configuration should be resolved.")
+}
+
+/// A registry item with files of its type, e.g. `("ui/button.rs", "pub struct Button;\n")`.
+pub fn registry_item(
+    name: &str,
+    r#type: &str,
+    files: &[(&str, &str)],
+    registry_dependencies: &[&str],
+) -> serde_json::Value {
+    serde_json::json!({
+        "name": name,
+        "type": format!("registry:{type}"),
+        "registryDependencies": registry_dependencies,
+        "files": files
+            .iter()
+            .map(|(path, content)| serde_json::json!({
+                "path": path,
+                "content": content,
+                "type": format!("registry:{type}"),
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// Write the items to the Leptos registry in `registry`, replacing earlier versions.
+pub fn write_registry(dir: &TempDir, items: &[serde_json::Value]) {
+    dir.write(
+        "registry/r/frameworks/leptos/index.json",
+        &serde_json::to_string(items).expect("Registry index should serialize."),
+    );
+    for item in items {
+        dir.write(
+            &format!(
+                "registry/r/frameworks/leptos/styles/default/{}.json",
+                item["name"]
+                    .as_str()
+                    .expect("Registry item should have a name.")
+            ),
+            &item.to_string(),
+        );
+    }
+}
+
+/// Write a Leptos project with the default configuration, which uses the registry in `registry` with the items.
+pub async fn leptos_project(dir: &TempDir, items: &[serde_json::Value]) -> Config {
+    dir.write(
+        "Cargo.toml",
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\nedition = \"2024\"\n\n[dependencies]\nleptos = \"0.8.0\"\n",
+    );
+    dir.write("src/main.rs", "fn main() {}\n");
+    dir.write(
+        CONFIG_FILE_NAME,
+        &toml::to_string(&raw_config(PathsConfig::default()))
+            .expect("Configuration should serialize."),
+    );
+    write_registry(dir, items);
+
+    let config = config(dir.path()).await;
+    set_registry_config(&config);
+    set_registry_url("file://registry");
+
+    config
+}
@@ -0,0 +1,46 @@
+pub mod transform_alias;
+pub mod transform_import;
+pub mod transform_inline_colors;
+pub mod transform_tw_prefix;
+pub mod tw_classes;
+
+use std::collections::HashMap;
+
+use shadcn_registry::schema::{FrameworkName, RegistryBaseColor, RegistryEntry};
+
+use crate::utils::{
+    get_config::Config,
+    transformers::{
+        transform_alias::transform_alias, transform_import::transform_import,
+        transform_inline_colors::transform_inline_colors, transform_tw_prefix::transform_tw_prefix,
+    },
+};
+
+pub struct TransformOptions<'a> {
+    pub config: &'a Config,
+    pub framework: FrameworkName,
+    /// Required to inline colors when CSS variables are disabled.
+    pub base_color: Option<&'a RegistryBaseColor>,
+    /// Identifiers to rename, e.g. `Button` to `FancyButton`.
+    pub renames: &'a HashMap<String, String>,
+}
+
+/// Transform a registry file's source so it compiles in the project.
+pub fn transform(content: &str, options: &TransformOptions) -> String {
+    let content = transform_import(content, options);
+    let content = transform_inline_colors(&content, options);
+    let content = transform_tw_prefix(&content, options);
+
+    transform_alias(&content, options)
+}
+
+/// Transform the files of all items in place.
+pub fn transform_items(items: &mut [RegistryEntry], options: &TransformOptions) {
+    for item in items {
+        for file in item.files.iter_mut().flatten() {
+            if let Some(content) = &file.content {
+                file.content = Some(transform(content, options));
+            }
+        }
+    }
+}
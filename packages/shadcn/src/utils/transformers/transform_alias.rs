@@ -0,0 +1,267 @@
+use std::{collections::HashMap, ops::Range};
+
+use convert_case::{Case, Casing};
+use regex::{Captures, Regex};
+use shadcn_registry::schema::RegistryEntry;
+
+use crate::utils::transformers::TransformOptions;
+
+/// Rename identifiers, e.g. `Button` and `ButtonProps` to `FancyButton` and `FancyButtonProps`.
+///
+/// Strings and comments are kept as they are.
+pub fn transform_alias(content: &str, options: &TransformOptions) -> String {
+    if options.renames.is_empty() {
+        return content.into();
+    }
+
+    let mut identifiers = options
+        .renames
+        .keys()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    // Prefer the longest identifier when several match.
+    identifiers.sort_by_key(|identifier| std::cmp::Reverse(identifier.len()));
+
+    let regex = Regex::new(&format!(
+        r"\b({})\b",
+        identifiers
+            .iter()
+            .map(|identifier| regex::escape(identifier))
+            .collect::<Vec<_>>()
+            .join("|")
+    ))
+    .expect("Regex should be valid.");
+
+    let skipped_ranges = find_strings_and_comments(content);
+
+    regex
+        .replace_all(content, |captures: &Captures| {
+            let start = captures.get(0).expect("Match should exist.").start();
+            if skipped_ranges.iter().any(|range| range.contains(&start)) {
+                captures[0].to_string()
+            } else {
+                options.renames[&captures[1]].clone()
+            }
+        })
+        .into_owned()
+}
+
+/// Find the byte ranges of string literals, character literals and comments in Rust source.
+fn find_strings_and_comments(content: &str) -> Vec<Range<usize>> {
+    let bytes = content.as_bytes();
+    let mut ranges = vec![];
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let start = index;
+        let rest = &content[index..];
+
+        let end = if rest.starts_with("//") {
+            rest.find('\n').map_or(bytes.len(), |end| index + end)
+        } else if rest.starts_with("/*") {
+            // Block comments can be nested.
+            let mut depth = 0;
+            let mut end = index;
+            while end < bytes.len() {
+                if bytes[end..].starts_with(b"/*") {
+                    depth += 1;
+                    end += 2;
+                } else if bytes[end..].starts_with(b"*/") {
+                    depth -= 1;
+                    end += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    end += 1;
+                }
+            }
+            end
+        } else if let Some(hashes) = raw_string_hashes(rest) {
+            let opening = rest.find('"').expect("Raw string should have a quote.") + 1;
+            let closing = format!("\"{}", "#".repeat(hashes));
+            rest[opening..]
+                .find(&closing)
+                .map_or(bytes.len(), |end| index + opening + end + closing.len())
+        } else if bytes[index] == b'"' {
+            closing_quote(bytes, index + 1, b'"')
+        } else if bytes[index] == b'\'' {
+            let mut characters = rest.chars().skip(1);
+            match (characters.next(), characters.next()) {
+                (Some('\\'), _) => closing_quote(bytes, index + 1, b'\''),
+                (Some(character), Some('\'')) => index + 1 + character.len_utf8() + 1,
+                // Lifetimes and labels, e.g. `'a`.
+                _ => {
+                    index += 1;
+                    continue;
+                }
+            }
+        } else {
+            index += rest.chars().next().map_or(1, char::len_utf8);
+            continue;
+        };
+
+        ranges.push(start..end);
+        index = end;
+    }
+
+    ranges
+}
+
+/// Get the number of `#` of a raw string starting at the beginning of `content`, e.g. `1` for `r#"`.
+fn raw_string_hashes(content: &str) -> Option<usize> {
+    let rest = content
+        .strip_prefix("br")
+        .or_else(|| content.strip_prefix('r'))?;
+    let hashes = rest.len() - rest.trim_start_matches('#').len();
+
+    rest[hashes..].starts_with('"').then_some(hashes)
+}
+
+/// Find the end of a literal after the unescaped closing quote.
+fn closing_quote(bytes: &[u8], mut index: usize, quote: u8) -> usize {
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' => index += 2,
+            byte if byte == quote => return index + 1,
+            _ => index += 1,
+        }
+    }
+
+    bytes.len()
+}
+
+/// Collect the public identifiers of aliased items which need to be renamed.
+///
+/// `aliases` maps item names to aliases, e.g. `button` to `FancyButton`. Identifiers declared by the item which start
+/// with its name in Pascal case, e.g. `Button` and `ButtonProps`, are renamed to start with the alias.
+pub fn get_alias_renames(
+    items: &[RegistryEntry],
+    aliases: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let declaration_regex =
+        Regex::new(r"\bpub\s+(?:struct|enum|fn|type|trait|const|static)\s+(\w+)")
+            .expect("Regex should be valid.");
+
+    let mut renames = HashMap::new();
+    for item in items {
+        let Some(alias) = aliases.get(&item.name) else {
+            continue;
+        };
+        let prefix = item.name.to_case(Case::Pascal);
+
+        for content in item
+            .files
+            .iter()
+            .flatten()
+            .filter_map(|file| file.content.as_ref())
+        {
+            for captures in declaration_regex.captures_iter(content) {
+                if let Some(suffix) = captures[1].strip_prefix(&prefix) {
+                    renames.insert(captures[1].to_string(), format!("{alias}{suffix}"));
+                }
+            }
+        }
+    }
+
+    renames
+}
+
+#[cfg(test)]
+mod tests {
+    use shadcn_registry::schema::FrameworkName;
+
+    use super::*;
+    use crate::utils::test_utils::{TempDir, config};
+
+    fn item(name: &str, content: &str) -> RegistryEntry {
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "type": "registry:ui",
+            "files": [{ "path": format!("ui/{name}.rs"), "type": "registry:ui", "content": content }],
+        }))
+        .expect("Item should be valid.")
+    }
+
+    #[tokio::test]
+    async fn renames_identifiers_outside_strings_and_comments() {
+        let dir = TempDir::new();
+        let config = config(dir.path()).await;
+        let renames = HashMap::from([
+            ("Button".to_string(), "Fancy".to_string()),
+            ("ButtonProps".to_string(), "FancyProps".to_string()),
+        ]);
+        let options = TransformOptions {
+            config: &config,
+            framework: FrameworkName::Leptos,
+            base_color: None,
+            renames: &renames,
+        };
+
+        assert_eq!(
+            transform_alias(
+                r##"/// A `Button`.
+#[component]
+pub fn Button<'a>(props: ButtonProps) -> impl IntoView {
+    // Button /* nested */ comment.
+    /* Button /* nested Button */ Button */
+    let label = "Button";
+    let raw = r#"Button "quoted" Button"#;
+    let quote = '"';
+    view! { <button aria-label="Button \"Button\"">{Button::label(quote)}</button> }
+}
+"##,
+                &options
+            ),
+            r##"/// A `Button`.
+#[component]
+pub fn Fancy<'a>(props: FancyProps) -> impl IntoView {
+    // Button /* nested */ comment.
+    /* Button /* nested Button */ Button */
+    let label = "Button";
+    let raw = r#"Button "quoted" Button"#;
+    let quote = '"';
+    view! { <button aria-label="Button \"Button\"">{Fancy::label(quote)}</button> }
+}
+"##
+        );
+        assert_eq!(
+            transform_alias("ButtonGroup(Button, ButtonProps)", &options),
+            "ButtonGroup(Fancy, FancyProps)"
+        );
+    }
+
+    #[test]
+    fn finds_strings_and_comments() {
+        let content = r#"let a = 'b'; // c
+let d = b"e\"f"; /* g — ü */ 'h: loop {}"#;
+
+        assert_eq!(
+            find_strings_and_comments(content)
+                .into_iter()
+                .map(|range| &content[range])
+                .collect::<Vec<_>>(),
+            ["'b'", "// c", r#""e\"f""#, "/* g — ü */"]
+        );
+    }
+
+    #[test]
+    fn renames_identifiers_of_aliased_items() {
+        let items = [
+            item(
+                "button",
+                "pub struct Button;\npub enum ButtonVariant {}\npub fn helper() {}\n",
+            ),
+            item("dialog", "pub struct Dialog;\n"),
+        ];
+        let aliases = HashMap::from([("button".to_string(), "Fancy".to_string())]);
+
+        assert_eq!(
+            get_alias_renames(&items, &aliases),
+            HashMap::from([
+                ("Button".to_string(), "Fancy".to_string()),
+                ("ButtonVariant".to_string(), "FancyVariant".to_string()),
+            ])
+        );
+    }
+}
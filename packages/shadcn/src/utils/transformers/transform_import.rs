@@ -0,0 +1,151 @@
+use std::{path::Path, sync::LazyLock};
+
+use regex::{Captures, Regex};
+use shadcn_registry::{
+    REGISTRY,
+    schema::{RegistryItemFile, RegistryItemType},
+};
+
+use crate::utils::{registry::get_registry_item_file_path, transformers::TransformOptions};
+
+static CRATE_PATH_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\bshadcn_ui_(?:dioxus|leptos|yew)_(\w+)(?:::(?:default|new_york))?\b")
+        .expect("Regex should be valid.")
+});
+
+/// Rewrite imports of other components, e.g. `shadcn_ui_yew_button::new_york::ButtonClass`,
+/// to the module the component is installed to, e.g. `crate::components::ui::button::ButtonClass`.
+pub fn transform_import(content: &str, options: &TransformOptions) -> String {
+    CRATE_PATH_REGEX
+        .replace_all(content, |captures: &Captures| {
+            component_module_path(&captures[1], options)
+        })
+        .into_owned()
+}
+
+fn component_module_path(crate_suffix: &str, options: &TransformOptions) -> String {
+    let name = crate_suffix.replace('_', "-");
+
+    // Components not in the compiled-in registry are assumed to be UI components.
+    let file = REGISTRY
+        .get(&options.framework)
+        .into_iter()
+        .flatten()
+        .find(|entry| entry.name == name)
+        .and_then(|entry| entry.files.as_ref()?.first().cloned())
+        .unwrap_or_else(|| RegistryItemFile {
+            path: format!("ui/{crate_suffix}.rs"),
+            content: None,
+            r#type: RegistryItemType::Ui,
+            target: None,
+        });
+
+    let file_path = get_registry_item_file_path(&file, options.config, None);
+    module_path(&file_path, &options.config.resolved_paths.cwd.join("src"))
+}
+
+/// Convert a file path in `src` to a module path, e.g. `src/components/ui/button.rs` to `crate::components::ui::button`.
+pub fn module_path(file_path: &Path, src: &Path) -> String {
+    let relative_path = file_path.strip_prefix(src).unwrap_or(file_path);
+
+    let mut segments = vec!["crate".to_string()];
+    segments.extend(
+        relative_path
+            .with_extension("")
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy().into_owned())
+            .filter(|segment| segment != "mod"),
+    );
+
+    segments.join("::")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use shadcn_registry::schema::FrameworkName;
+
+    use super::*;
+    use crate::utils::test_utils::{TempDir, config};
+
+    #[tokio::test]
+    async fn rewrites_component_crates() {
+        let dir = TempDir::new();
+        dir.write("src/main.rs", "fn main() {}\n");
+        let config = config(dir.path()).await;
+        let renames = HashMap::new();
+
+        for framework in [
+            FrameworkName::Dioxus,
+            FrameworkName::Leptos,
+            FrameworkName::Yew,
+        ] {
+            let options = TransformOptions {
+                config: &config,
+                framework,
+                base_color: None,
+                renames: &renames,
+            };
+
+            for style in ["", "::default", "::new_york"] {
+                assert_eq!(
+                    transform_import(
+                        &format!("use shadcn_ui_{framework}_button{style}::ButtonClass;"),
+                        &options
+                    ),
+                    "use crate::components::ui::button::ButtonClass;"
+                );
+                assert_eq!(
+                    transform_import(
+                        &format!(
+                            "use shadcn_ui_{framework}_alert_dialog{style}::{{AlertDialog, AlertDialogTitle}};"
+                        ),
+                        &options
+                    ),
+                    "use crate::components::ui::alert_dialog::{AlertDialog, AlertDialogTitle};"
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn rewrites_nested_use_groups() {
+        let dir = TempDir::new();
+        dir.write("src/lib.rs", "");
+        let config = config(dir.path()).await;
+        let renames = HashMap::new();
+        let options = TransformOptions {
+            config: &config,
+            framework: FrameworkName::Leptos,
+            base_color: None,
+            renames: &renames,
+        };
+
+        assert_eq!(
+            transform_import(
+                "use {\n    leptos::prelude::*,\n    shadcn_ui_leptos_button::new_york::{Button, ButtonVariant},\n    shadcn_ui_leptos_utils::cn,\n};\n\nlet class = shadcn_ui_leptos_utils::cn(\"p-2\");",
+                &options
+            ),
+            "use {\n    leptos::prelude::*,\n    crate::components::ui::button::{Button, ButtonVariant},\n    crate::common::utils::cn,\n};\n\nlet class = crate::common::utils::cn(\"p-2\");"
+        );
+        assert_eq!(
+            transform_import("use my_shadcn_ui_leptos_button::Button;", &options),
+            "use my_shadcn_ui_leptos_button::Button;"
+        );
+    }
+
+    #[test]
+    fn converts_paths_to_modules() {
+        let src = Path::new("/app/src");
+
+        assert_eq!(
+            module_path(Path::new("/app/src/components/ui/button.rs"), src),
+            "crate::components::ui::button"
+        );
+        assert_eq!(
+            module_path(Path::new("/app/src/lib/mod.rs"), src),
+            "crate::lib"
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use shadcn_registry::schema::{Mode, RegistryItemCssVars};
+
+use crate::utils::transformers::{
+    TransformOptions,
+    tw_classes::{replace_tw_classes, split_class},
+};
+
+/// Utilities which take a color, e.g. `bg-primary`. Longer utilities come first, so `ring-offset-` is matched before `ring-`.
+const COLOR_UTILITIES: [&str; 17] = [
+    "ring-offset-",
+    "placeholder-",
+    "decoration-",
+    "outline-",
+    "divide-",
+    "accent-",
+    "border-",
+    "shadow-",
+    "stroke-",
+    "caret-",
+    "ring-",
+    "text-",
+    "fill-",
+    "from-",
+    "via-",
+    "bg-",
+    "to-",
+];
+
+/// Replace semantic color classes in the class lists of a component (see [`find_class_lists`](super::tw_classes::find_class_lists)) with the base color's Tailwind colors when CSS variables are disabled,
+/// e.g. `bg-primary` to `bg-slate-900 dark:bg-slate-50`.
+pub fn transform_inline_colors(content: &str, options: &TransformOptions) -> String {
+    if options.config.tailwind.css_variables {
+        return content.into();
+    }
+    let Some(base_color) = options.base_color else {
+        return content.into();
+    };
+
+    replace_tw_classes(content, |classes| {
+        apply_color_mapping(classes, &base_color.inline_colors)
+    })
+}
+
+/// Map the semantic colors in a space-separated list of classes, adding `dark:` variants for dark mode.
+pub fn apply_color_mapping(classes: &str, inline_colors: &RegistryItemCssVars) -> String {
+    let empty = HashMap::new();
+    let light = inline_colors.get(&Mode::Light).unwrap_or(&empty);
+    let dark = inline_colors.get(&Mode::Dark).unwrap_or(&empty);
+
+    let mut result: Vec<String> = vec![];
+    let mut push = |class: String| {
+        if !result.contains(&class) {
+            result.push(class);
+        }
+    };
+
+    for class in classes.split_whitespace() {
+        let (variants, utility) = split_class(class);
+        let Some((utility, color, modifier)) = split_color_utility(utility) else {
+            push(class.into());
+            continue;
+        };
+
+        // Classes which only apply in dark mode are mapped with the dark colors only.
+        if variants.split(':').any(|variant| variant == "dark") {
+            match dark.get(color) {
+                Some(color) => push(format!("{variants}{utility}{color}{modifier}")),
+                None => push(class.into()),
+            }
+            continue;
+        }
+
+        match light.get(color) {
+            Some(color) => push(format!("{variants}{utility}{color}{modifier}")),
+            None => push(class.into()),
+        }
+        if let Some(color) = dark.get(color) {
+            push(format!("dark:{variants}{utility}{color}{modifier}"));
+        }
+    }
+
+    result.join(" ")
+}
+
+/// Split a color utility into the utility, color and opacity modifier, e.g. `!bg-primary/80` into `!bg-`, `primary` and `/80`.
+fn split_color_utility(utility: &str) -> Option<(&str, &str, &str)> {
+    let important = if utility.starts_with('!') { 1 } else { 0 };
+    let name = COLOR_UTILITIES
+        .iter()
+        .find(|name| utility[important..].starts_with(*name))?;
+
+    let (utility, value) = utility.split_at(important + name.len());
+    let (color, modifier) = value.split_at(value.find('/').unwrap_or(value.len()));
+
+    Some((utility, color, modifier))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inline_colors() -> RegistryItemCssVars {
+        HashMap::from([
+            (
+                Mode::Light,
+                HashMap::from([
+                    ("background".into(), "white".into()),
+                    ("primary".into(), "slate-900".into()),
+                ]),
+            ),
+            (
+                Mode::Dark,
+                HashMap::from([
+                    ("background".into(), "slate-950".into()),
+                    ("primary".into(), "slate-50".into()),
+                ]),
+            ),
+        ])
+    }
+
+    #[test]
+    fn maps_colors() {
+        let inline_colors = inline_colors();
+
+        assert_eq!(
+            apply_color_mapping("flex bg-primary/80 hover:text-primary", &inline_colors),
+            "flex bg-slate-900/80 dark:bg-slate-50/80 hover:text-slate-900 dark:hover:text-slate-50"
+        );
+        assert_eq!(
+            apply_color_mapping("dark:bg-primary bg-muted", &inline_colors),
+            "dark:bg-slate-50 bg-muted"
+        );
+    }
+
+    #[test]
+    fn maps_colors_at_every_class_site() {
+        let inline_colors = inline_colors();
+        let content = r#"#[tw(class = "bg-primary")]
+html! {
+    <span class={tw_merge!{"bg-background", class}}>
+        <span class="text-primary" />
+    </span>
+}"#;
+
+        assert_eq!(
+            replace_tw_classes(content, |classes| apply_color_mapping(
+                classes,
+                &inline_colors
+            )),
+            r#"#[tw(class = "bg-slate-900 dark:bg-slate-50")]
+html! {
+    <span class={tw_merge!{"bg-white dark:bg-slate-950", class}}>
+        <span class="text-slate-900 dark:text-slate-50" />
+    </span>
+}"#
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use crate::utils::transformers::{
+    TransformOptions,
+    tw_classes::{replace_tw_classes, split_class},
+};
+
+/// Prefix the Tailwind classes in the class lists of a component, see [`find_class_lists`](super::tw_classes::find_class_lists).
+pub fn transform_tw_prefix(content: &str, options: &TransformOptions) -> String {
+    let prefix = &options.config.tailwind.prefix;
+    if prefix.is_empty() {
+        return content.into();
+    }
+
+    replace_tw_classes(content, |classes| apply_prefix(classes, prefix))
+}
+
+/// Prefix each class in a space-separated list, e.g. `hover:bg-primary` to `hover:tw-bg-primary`.
+pub fn apply_prefix(classes: &str, prefix: &str) -> String {
+    classes
+        .split_whitespace()
+        .map(|class| prefix_class(class, prefix))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn prefix_class(class: &str, prefix: &str) -> String {
+    // Tailwind CSS v4 prefixes look like variants and come first, e.g. `tw:hover:bg-primary`.
+    if prefix.ends_with(':') {
+        return if class.starts_with(prefix) {
+            class.into()
+        } else {
+            format!("{prefix}{class}")
+        };
+    }
+
+    let (variants, utility) = split_class(class);
+    let (important, utility) = match utility.strip_prefix('!') {
+        Some(utility) => ("!", utility),
+        None => ("", utility),
+    };
+    let (negative, utility) = match utility.strip_prefix('-') {
+        Some(utility) => ("-", utility),
+        None => ("", utility),
+    };
+
+    if utility.is_empty() || utility.starts_with(prefix) {
+        return class.into();
+    }
+
+    format!("{variants}{important}{negative}{prefix}{utility}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefixes_classes() {
+        assert_eq!(
+            apply_prefix("flex hover:!-mt-2 data-[state=checked]:bg-primary", "tw-"),
+            "tw-flex hover:!-tw-mt-2 data-[state=checked]:tw-bg-primary"
+        );
+        assert_eq!(apply_prefix("tw-flex", "tw-"), "tw-flex");
+        assert_eq!(
+            apply_prefix("flex hover:p-2", "tw:"),
+            "tw:flex tw:hover:p-2"
+        );
+    }
+
+    #[test]
+    fn prefixes_every_class_site() {
+        let content = r#"#[tw(class = "flex")]
+html! { <span class={tw_merge!{"p-2", class}}><span class="sr-only" /></span> }"#;
+
+        assert_eq!(
+            replace_tw_classes(content, |classes| apply_prefix(classes, "tw-")),
+            r#"#[tw(class = "tw-flex")]
+html! { <span class={tw_merge!{"tw-p-2", class}}><span class="tw-sr-only" /></span> }"#
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use std::{ops::Range, sync::LazyLock};
+
+use regex::Regex;
+
+static CLASS_ARGUMENT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\bclass\s*[=:]\s*"([^"\\]*)""#).expect("Regex should be valid.")
+});
+static STRING_LITERAL_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#""([^"\\]*)""#).expect("Regex should be valid."));
+static TW_MERGE_MACRO_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\btw_merge!\s*([(\[{])").expect("Regex should be valid."));
+
+/// Find the class lists in a component, as the ranges of their string literals' content.
+///
+/// Class lists are the `class` arguments of `#[tw(...)]` attributes, the string literals in `tw_merge!` invocations
+/// with any delimiter and literal `class="..."` attributes in `view!` and `html!`.
+pub fn find_class_lists(content: &str) -> Vec<Range<usize>> {
+    let mut ranges = vec![];
+
+    // Also matches the `class = "..."` arguments of `#[tw(...)]` attributes.
+    for captures in CLASS_ARGUMENT_REGEX.captures_iter(content) {
+        ranges.push(captures.get(1).expect("Group should exist.").range());
+    }
+
+    for captures in TW_MERGE_MACRO_REGEX.captures_iter(content) {
+        let delimiter = captures.get(1).expect("Group should exist.");
+        let arguments_start = delimiter.end();
+        let arguments_end =
+            arguments_start + closing_delimiter(&content[arguments_start..], delimiter.as_str());
+
+        for literal in STRING_LITERAL_REGEX.captures_iter(&content[arguments_start..arguments_end])
+        {
+            let range = literal.get(1).expect("Group should exist.").range();
+            ranges.push(arguments_start + range.start..arguments_start + range.end);
+        }
+    }
+
+    ranges.sort_by_key(|range| range.start);
+    ranges.dedup();
+
+    ranges
+}
+
+/// Replace the class lists found by [`find_class_lists`].
+pub fn replace_tw_classes<F>(content: &str, replace: F) -> String
+where
+    F: Fn(&str) -> String,
+{
+    let mut result = String::with_capacity(content.len());
+    let mut end = 0;
+    for range in find_class_lists(content) {
+        result.push_str(&content[end..range.start]);
+        result.push_str(&replace(&content[range.clone()]));
+        end = range.end;
+    }
+    result.push_str(&content[end..]);
+
+    result
+}
+
+/// Find the index of the delimiter closing the macro invocation, or the end of the input if there is none.
+fn closing_delimiter(content: &str, opening: &str) -> usize {
+    let (open, close) = match opening {
+        "{" => ('{', '}'),
+        "[" => ('[', ']'),
+        _ => ('(', ')'),
+    };
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (index, char) in content.char_indices() {
+        match char {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            _ if in_string => {}
+            _ if char == open => depth += 1,
+            _ if char == close && depth == 0 => return index,
+            _ if char == close => depth -= 1,
+            _ => {}
+        }
+    }
+
+    content.len()
+}
+
+/// Split a class into its variants, including the trailing colon, and its utility,
+/// e.g. `data-[state=checked]:hover:bg-primary` into `data-[state=checked]:hover:` and `bg-primary`.
+pub fn split_class(class: &str) -> (&str, &str) {
+    // Variants such as `data-[state=checked]:` and `[&_svg]:` may contain colons inside brackets.
+    let mut depth = 0;
+    let mut utility_start = 0;
+    for (index, char) in class.char_indices() {
+        match char {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            ':' if depth == 0 => utility_start = index + 1,
+            _ => {}
+        }
+    }
+
+    class.split_at(utility_start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn upper(content: &str) -> String {
+        replace_tw_classes(content, |classes| classes.to_uppercase())
+    }
+
+    #[test]
+    fn replaces_tw_attribute() {
+        assert_eq!(
+            upper("#[tw(\n    class = \"flex\",\n    variant(class = \"p-2\")\n)]"),
+            "#[tw(\n    class = \"FLEX\",\n    variant(class = \"P-2\")\n)]"
+        );
+    }
+
+    #[test]
+    fn replaces_tw_merge_with_any_delimiter() {
+        assert_eq!(
+            upper(r#"class={tw_merge!("flex", class)}"#),
+            r#"class={tw_merge!("FLEX", class)}"#
+        );
+        assert_eq!(
+            upper("class={tw_merge!{\n    \"flex\",\n    class\n}}"),
+            "class={tw_merge!{\n    \"FLEX\",\n    class\n}}"
+        );
+        assert_eq!(
+            upper(r#"class=move || tw_merge! ["flex", class.get()]"#),
+            r#"class=move || tw_merge! ["FLEX", class.get()]"#
+        );
+    }
+
+    #[test]
+    fn replaces_literal_class_attributes() {
+        assert_eq!(
+            upper(r#"<div class="relative w-full"><span class="sr-only">{"More"}</span></div>"#),
+            r#"<div class="RELATIVE W-FULL"><span class="SR-ONLY">{"More"}</span></div>"#
+        );
+        assert_eq!(
+            upper(r#"div { class: "flex" }"#),
+            r#"div { class: "FLEX" }"#
+        );
+    }
+
+    #[test]
+    fn ignores_other_strings() {
+        let content = r#"let label = "flex"; html! { <div class={class} aria-label="flex" /> }"#;
+        assert_eq!(upper(content), content);
+    }
+
+    #[test]
+    fn stops_at_closing_delimiter() {
+        assert_eq!(
+            upper(r#"tw_merge!{"flex", if a { "p-2" } else { "p-4" }} "text""#),
+            r#"tw_merge!{"FLEX", if a { "P-2" } else { "P-4" }} "text""#
+        );
+        assert_eq!(
+            upper(r#"tw_merge!("a)", "b") "c""#),
+            r#"tw_merge!("A)", "B") "c""#
+        );
+    }
+
+    #[test]
+    fn splits_variants() {
+        assert_eq!(
+            split_class("data-[state=checked]:hover:bg-primary"),
+            ("data-[state=checked]:hover:", "bg-primary")
+        );
+        assert_eq!(split_class("[&_svg]:size-4"), ("[&_svg]:", "size-4"));
+        assert_eq!(split_class("flex"), ("", "flex"));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod update_css_vars;
+pub mod update_dependencies;
+pub mod update_files;
@@ -0,0 +1,514 @@
+use std::{collections::HashMap, ops::Range};
+
+use anyhow::{Result, bail};
+use shadcn_registry::schema::RegistryBaseColor;
+
+use crate::utils::{
+    get_config::Config,
+    get_project_info::TailwindVersion,
+    highlighter::HIGHLIGHTER,
+    logger::LOGGER,
+    plan::{path_exists, read_file, write_file},
+    spinner::{SpinnerOptions, spinner},
+};
+
+pub struct UpdateCssVarsOptions {
+    pub silent: bool,
+    pub tailwind_version: TailwindVersion,
+}
+
+/// Merge the base color's styles into the Tailwind CSS file, keeping its other styles.
+pub async fn update_css_vars(
+    base_color: &RegistryBaseColor,
+    config: &Config,
+    options: UpdateCssVarsOptions,
+) -> Result<()> {
+    let css_path = &config.resolved_paths.tailwind_css;
+    let relative_path = css_path
+        .strip_prefix(&config.resolved_paths.cwd)
+        .unwrap_or(css_path);
+
+    let mut css_vars_spinner = spinner(
+        format!(
+            "Updating {}",
+            HIGHLIGHTER.info(&relative_path.to_string_lossy())
+        ),
+        SpinnerOptions {
+            silent: options.silent,
+        },
+    );
+
+    let content = match (options.tailwind_version, config.tailwind.css_variables) {
+        (TailwindVersion::V3, true) => &base_color.css_vars_template,
+        (TailwindVersion::V3, false) => &base_color.inline_colors_template,
+        (TailwindVersion::V4, true) => &base_color.css_vars_template_v4,
+        (TailwindVersion::V4, false) => &base_color.inline_colors_template_v4,
+    };
+    if content.is_empty() {
+        css_vars_spinner.fail();
+
+        LOGGER.r#break();
+        LOGGER.error("The registry does not provide styles for Tailwind CSS v4.");
+        LOGGER.r#break();
+
+        bail!("");
+    }
+
+    let content = if path_exists(css_path).await? {
+        merge_css(&read_file(css_path).await?, content)
+    } else {
+        content.clone()
+    };
+    write_file(css_path, content).await?;
+
+    css_vars_spinner.succeed(None);
+
+    Ok(())
+}
+
+/// A statement or rule of a style sheet, e.g. `@import "tailwindcss";`, `--radius: 0.5rem;` or `:root { ... }`.
+struct CssNode {
+    /// The node without surrounding whitespace and comments.
+    range: Range<usize>,
+    /// The statement without its semicolon, or the selector or at-rule before the block, with normalized whitespace.
+    prelude: String,
+    block: Option<CssBlock>,
+    /// Whether the statement ends with a semicolon.
+    terminated: bool,
+}
+
+struct CssBlock {
+    /// The content between the braces.
+    body: Range<usize>,
+    nodes: Vec<CssNode>,
+}
+
+/// Merge the statements and rules of a template into a style sheet.
+///
+/// Existing declarations are updated, missing statements and rules are added and everything else is kept as is.
+/// Rules are matched by their selector or at-rule, in order of occurrence.
+pub fn merge_css(content: &str, template: &str) -> String {
+    if content.trim().is_empty() {
+        return template.into();
+    }
+
+    let mut edits = vec![];
+    merge_nodes(
+        content,
+        &parse_css(content, 0..content.len()),
+        None,
+        template,
+        &parse_css(template, 0..template.len()),
+        &mut edits,
+    );
+
+    // Insertions come before replacements at the same position.
+    edits.sort_by_key(|(range, _)| (range.start, range.end));
+
+    let mut result = String::with_capacity(content.len());
+    let mut end = 0;
+    for (range, text) in edits {
+        result.push_str(&content[end..range.start]);
+        result.push_str(&text);
+        end = range.end;
+    }
+    result.push_str(&content[end..]);
+
+    result
+}
+
+fn merge_nodes(
+    content: &str,
+    nodes: &[CssNode],
+    parent: Option<&CssNode>,
+    template: &str,
+    template_nodes: &[CssNode],
+    edits: &mut Vec<(Range<usize>, String)>,
+) {
+    let indent = match (nodes.first(), parent) {
+        (Some(node), _) => line_indent(content, node.range.start),
+        (None, Some(parent)) => format!(
+            "{}{}",
+            line_indent(content, parent.range.start),
+            indent_unit(content)
+        ),
+        (None, None) => String::new(),
+    };
+
+    let mut statements: Vec<String> = vec![];
+    let mut blocks: Vec<String> = vec![];
+    let mut occurrences: HashMap<&str, usize> = HashMap::new();
+    for template_node in template_nodes {
+        let Some(template_block) = &template_node.block else {
+            let key = statement_key(&template_node.prelude);
+            let statement = format!("{};", template_node.prelude);
+            match nodes
+                .iter()
+                .find(|node| node.block.is_none() && statement_key(&node.prelude) == key)
+            {
+                Some(node) if node.prelude != template_node.prelude => {
+                    edits.push((node.range.clone(), statement))
+                }
+                Some(_) => {}
+                None => statements.push(statement),
+            }
+            continue;
+        };
+
+        let occurrence = occurrences
+            .entry(template_node.prelude.as_str())
+            .or_default();
+        let node = nodes
+            .iter()
+            .filter(|node| node.block.is_some() && node.prelude == template_node.prelude)
+            .nth(*occurrence);
+        *occurrence += 1;
+
+        match node {
+            Some(
+                node @ CssNode {
+                    block: Some(block), ..
+                },
+            ) => merge_nodes(
+                content,
+                &block.nodes,
+                Some(node),
+                template,
+                &template_block.nodes,
+                edits,
+            ),
+            _ => blocks.push(reindent(
+                &template[template_node.range.clone()],
+                &line_indent(template, template_node.range.start),
+                &indent,
+            )),
+        }
+    }
+
+    if statements.is_empty() && blocks.is_empty() {
+        return;
+    }
+
+    let Some(last) = nodes.last() else {
+        let items = statements.iter().chain(&blocks);
+        let Some((parent, block)) =
+            parent.and_then(|parent| Some((parent, parent.block.as_ref()?)))
+        else {
+            // The style sheet only contains comments.
+            edits.push((
+                content.len()..content.len(),
+                items.map(|item| format!("\n\n{item}")).collect(),
+            ));
+            return;
+        };
+
+        let items = items
+            .map(|item| format!("\n{indent}{item}"))
+            .collect::<String>();
+        if content[block.body.clone()].trim().is_empty() {
+            edits.push((
+                block.body.clone(),
+                format!("{items}\n{}", line_indent(content, parent.range.start)),
+            ));
+        } else {
+            edits.push((block.body.start..block.body.start, items));
+        }
+        return;
+    };
+
+    // Statements such as `@import` come before the rules, after the existing statements.
+    if !statements.is_empty() {
+        match nodes.iter().take_while(|node| node.block.is_none()).last() {
+            Some(anchor) => edits.push((
+                anchor.range.end..anchor.range.end,
+                format!(
+                    "{}{}",
+                    if anchor.terminated { "" } else { ";" },
+                    statements
+                        .iter()
+                        .map(|statement| format!("\n{indent}{statement}"))
+                        .collect::<String>()
+                ),
+            )),
+            None => edits.push((
+                nodes[0].range.start..nodes[0].range.start,
+                format!("{}\n\n{indent}", statements.join(&format!("\n{indent}"))),
+            )),
+        }
+    }
+
+    if !blocks.is_empty() {
+        edits.push((
+            last.range.end..last.range.end,
+            format!(
+                "{}{}",
+                if last.terminated { "" } else { ";" },
+                blocks
+                    .iter()
+                    .map(|block| format!("\n\n{indent}{block}"))
+                    .collect::<String>()
+            ),
+        ));
+    }
+}
+
+/// Parse the statements and rules in a range of a style sheet.
+fn parse_css(content: &str, range: Range<usize>) -> Vec<CssNode> {
+    let mut nodes = vec![];
+    let mut index = range.start;
+
+    while index < range.end {
+        let start = skip_whitespace_and_comments(content, index, range.end);
+        if start >= range.end {
+            break;
+        }
+
+        let delimiter = find_outside(content, start, range.end, b";{");
+        if delimiter < range.end && content.as_bytes()[delimiter] == b'{' {
+            let close = closing_brace(content, delimiter + 1, range.end);
+            let body = delimiter + 1..close;
+            nodes.push(CssNode {
+                range: start..(close + 1).min(range.end),
+                prelude: normalize_whitespace(&content[start..delimiter]),
+                block: Some(CssBlock {
+                    nodes: parse_css(content, body.clone()),
+                    body,
+                }),
+                terminated: true,
+            });
+            index = close + 1;
+        } else {
+            let terminated = delimiter < range.end;
+            let text = &content[start..delimiter];
+            nodes.push(CssNode {
+                range: start..if terminated {
+                    delimiter + 1
+                } else {
+                    start + text.trim_end().len()
+                },
+                prelude: normalize_whitespace(text),
+                block: None,
+                terminated,
+            });
+            index = delimiter + 1;
+        }
+    }
+
+    nodes
+}
+
+fn skip_whitespace_and_comments(content: &str, mut index: usize, end: usize) -> usize {
+    loop {
+        let rest = content[index..end].trim_start();
+        index = end - rest.len();
+
+        if !rest.starts_with("/*") {
+            return index;
+        }
+        index = match content[index + 2..end].find("*/") {
+            Some(comment_end) => index + 2 + comment_end + 2,
+            None => end,
+        };
+    }
+}
+
+/// Find the first of the delimiters outside strings, comments and parentheses, or the end of the range.
+fn find_outside(content: &str, start: usize, end: usize, delimiters: &[u8]) -> usize {
+    let bytes = content.as_bytes();
+    let mut depth = 0;
+    let mut quote = None;
+    let mut index = start;
+
+    while index < end {
+        let byte = bytes[index];
+        match quote {
+            Some(_) if byte == b'\\' => index += 1,
+            Some(open) if byte == open => quote = None,
+            Some(_) => {}
+            None if byte == b'"' || byte == b'\'' => quote = Some(byte),
+            None if bytes[index..end].starts_with(b"/*") => {
+                index = skip_whitespace_and_comments(content, index, end);
+                continue;
+            }
+            None if byte == b'(' => depth += 1,
+            None if byte == b')' => depth -= 1,
+            None if depth == 0 && delimiters.contains(&byte) => return index,
+            None => {}
+        }
+        index += 1;
+    }
+
+    end
+}
+
+/// Find the brace closing a block which starts at `start`, or the end of the range.
+fn closing_brace(content: &str, start: usize, end: usize) -> usize {
+    let mut depth = 0;
+    let mut index = start;
+
+    loop {
+        index = find_outside(content, index, end, b"{}");
+        match content.as_bytes().get(index) {
+            _ if index >= end => return end,
+            Some(b'{') => depth += 1,
+            _ if depth == 0 => return index,
+            _ => depth -= 1,
+        }
+        index += 1;
+    }
+}
+
+/// Get the property of a declaration, or the whole statement for at-rules, e.g. `--radius` for `--radius: 0.5rem`.
+fn statement_key(prelude: &str) -> &str {
+    if prelude.starts_with('@') {
+        return prelude;
+    }
+
+    prelude
+        .split_once(':')
+        .map_or(prelude, |(property, _)| property.trim_end())
+}
+
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Get the indentation of the line on which a node starts.
+fn line_indent(content: &str, index: usize) -> String {
+    let line_start = content[..index]
+        .rfind('\n')
+        .map_or(0, |newline| newline + 1);
+    let indent = &content[line_start..index];
+
+    if indent.trim().is_empty() {
+        indent.into()
+    } else {
+        String::new()
+    }
+}
+
+/// Get the smallest indentation of the style sheet, four spaces if it is not indented.
+fn indent_unit(content: &str) -> &str {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+        .filter(|indent| !indent.is_empty())
+        .min_by_key(|indent| indent.len())
+        .unwrap_or("    ")
+}
+
+/// Replace the indentation of the lines after the first.
+fn reindent(text: &str, from: &str, to: &str) -> String {
+    text.split('\n')
+        .enumerate()
+        .map(|(index, line)| match line.strip_prefix(from) {
+            _ if index == 0 || line.is_empty() => line.into(),
+            Some(line) => format!("{to}{line}"),
+            None => format!("{to}{}", line.trim_start()),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = r#"@import "tailwindcss";
+
+@custom-variant dark (&:is(.dark *));
+
+:root {
+    --background: oklch(1 0 0);
+    --radius: 0.5rem;
+}
+
+.dark {
+    --background: oklch(0.1288 0.0406 264.695);
+}
+
+@layer base {
+    body {
+        @apply bg-background text-foreground;
+    }
+}
+"#;
+
+    #[test]
+    fn writes_template_to_empty_file() {
+        assert_eq!(merge_css("", TEMPLATE), TEMPLATE);
+        assert_eq!(merge_css("\n", TEMPLATE), TEMPLATE);
+    }
+
+    #[test]
+    fn keeps_merged_file() {
+        assert_eq!(merge_css(TEMPLATE, TEMPLATE), TEMPLATE);
+    }
+
+    #[test]
+    fn merges_into_existing_file() {
+        let content = r#"@import "tailwindcss";
+@import "./fonts.css";
+
+/* Brand colors. */
+:root {
+  --background: white;
+  --brand: #ff3e00;
+}
+
+.prose {
+  max-width: 65ch;
+}
+"#;
+
+        assert_eq!(
+            merge_css(content, TEMPLATE),
+            r#"@import "tailwindcss";
+@import "./fonts.css";
+@custom-variant dark (&:is(.dark *));
+
+/* Brand colors. */
+:root {
+  --background: oklch(1 0 0);
+  --brand: #ff3e00;
+  --radius: 0.5rem;
+}
+
+.prose {
+  max-width: 65ch;
+}
+
+.dark {
+    --background: oklch(0.1288 0.0406 264.695);
+}
+
+@layer base {
+    body {
+        @apply bg-background text-foreground;
+    }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn merges_into_nested_rules() {
+        let content =
+            "@tailwind base;\n\n@layer base {\n  body {\n    margin: 0\n  }\n  :root {}\n}\n";
+        let template = "@tailwind base;\n@tailwind utilities;\n\n@layer base {\n    :root {\n        --radius: 0.5rem;\n    }\n\n    body {\n        @apply bg-background;\n    }\n}\n";
+
+        assert_eq!(
+            merge_css(content, template),
+            "@tailwind base;\n@tailwind utilities;\n\n@layer base {\n  body {\n    margin: 0;\n    @apply bg-background;\n  }\n  :root {\n    --radius: 0.5rem;\n  }\n}\n"
+        );
+    }
+
+    #[test]
+    fn adds_statements_before_rules() {
+        assert_eq!(
+            merge_css("body {\n    margin: 0;\n}\n", "@import \"tailwindcss\";\n"),
+            "@import \"tailwindcss\";\n\nbody {\n    margin: 0;\n}\n"
+        );
+    }
+}
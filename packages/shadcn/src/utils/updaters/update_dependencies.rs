@@ -0,0 +1,609 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use semver::Version;
+use serde::Deserialize;
+use shadcn_registry::schema::{CrateDependency, RegistryItemDependency};
+use toml_edit::{Array, DocumentMut, InlineTable, Item, Table, Value, value};
+
+use crate::utils::{
+    get_config::Config,
+    get_project_info::get_workspace_members,
+    highlighter::HIGHLIGHTER,
+    logger::LOGGER,
+    plan::{canonicalize, path_exists, read_file, record_dependencies, write_file},
+    spinner::{SpinnerOptions, spinner},
+};
+
+const CRATES_IO_INDEX_URL: &str = "https://index.crates.io";
+
+pub struct UpdateDependenciesOptions {
+    pub silent: bool,
+}
+
+/// Add the dependencies to the project's `Cargo.toml`, preserving its formatting.
+pub async fn update_dependencies(
+    dependencies: &[RegistryItemDependency],
+    config: &Config,
+    options: UpdateDependenciesOptions,
+) -> Result<()> {
+    if dependencies.is_empty() {
+        return Ok(());
+    }
+
+    let mut dependencies_spinner = spinner(
+        "Installing dependencies.",
+        SpinnerOptions {
+            silent: options.silent,
+        },
+    );
+
+    if let Err(error) = update_manifest(dependencies, config).await {
+        dependencies_spinner.fail();
+
+        LOGGER.r#break();
+        LOGGER.error("Failed to install dependencies.");
+        LOGGER.error(&error.to_string());
+        LOGGER.r#break();
+
+        bail!("");
+    }
+
+    dependencies_spinner.succeed(None);
+
+    Ok(())
+}
+
+async fn update_manifest(dependencies: &[RegistryItemDependency], config: &Config) -> Result<()> {
+    let manifest_path = find_package_manifest(config).await?;
+    let content = read_file(&manifest_path)
+        .await
+        .with_context(|| format!("Failed to read {}.", manifest_path.display()))?;
+    let mut manifest = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse {}.", manifest_path.display()))?;
+
+    let workspace_dependencies = get_workspace_dependencies(
+        manifest_path
+            .parent()
+            .expect("Manifest path should have a parent."),
+    )
+    .await?;
+
+    let table = manifest
+        .entry("dependencies")
+        .or_insert_with(|| Item::Table(Table::new()))
+        .as_table_like_mut()
+        .with_context(|| {
+            format!(
+                "Invalid `dependencies` table in {}.",
+                manifest_path.display()
+            )
+        })?;
+
+    let mut added = vec![];
+    for (name, dependency) in merge_dependencies(dependencies) {
+        let features = dependency
+            .features
+            .iter()
+            .flatten()
+            .cloned()
+            .collect::<BTreeSet<_>>();
+
+        // Respect existing dependencies, including renamed ones, and only add missing features.
+        let existing_key = table
+            .iter()
+            .find(|(key, item)| {
+                item.get("package")
+                    .and_then(|package| package.as_str())
+                    .unwrap_or(key)
+                    == name
+            })
+            .map(|(key, _)| key.to_string());
+
+        if let Some(key) = existing_key {
+            let item = table.get_mut(&key).expect("Dependency should exist.");
+            add_features(item, &features);
+            continue;
+        }
+
+        let mut entry = InlineTable::new();
+        let is_workspace = dependency.git.is_none() && workspace_dependencies.contains(&name);
+        if is_workspace {
+            entry.insert("workspace", true.into());
+        } else if let Some(git) = &dependency.git {
+            if let Some(version) = &dependency.version {
+                entry.insert("version", version.into());
+            }
+            entry.insert("git", git.into());
+            for (key, value) in [
+                ("branch", &dependency.branch),
+                ("tag", &dependency.tag),
+                ("rev", &dependency.rev),
+            ] {
+                if let Some(value) = value {
+                    entry.insert(key, value.into());
+                }
+            }
+        } else {
+            let version = match &dependency.version {
+                Some(version) => version.clone(),
+                None => get_latest_version(&name).await?,
+            };
+
+            if features.is_empty() && dependency.default_features.is_none() {
+                table.insert(&name, value(version));
+                added.push(name);
+                continue;
+            }
+            entry.insert("version", version.into());
+        }
+        // Default features of workspace dependencies are set in the workspace.
+        if let Some(default_features) = dependency.default_features
+            && !is_workspace
+        {
+            entry.insert("default-features", default_features.into());
+        }
+        if !features.is_empty() {
+            entry.insert("features", Value::Array(features_array(&features)));
+        }
+
+        table.insert(&name, value(entry));
+        added.push(name);
+    }
+    record_dependencies(&added, &[]);
+
+    let updated_content = manifest.to_string();
+    if updated_content != content {
+        write_file(&manifest_path, updated_content).await?;
+    }
+
+    Ok(())
+}
+
+/// Remove the dependencies from the project's `Cargo.toml`, preserving its formatting.
+///
+/// Returns the names of the crates which were removed.
+pub async fn remove_dependencies(
+    names: &[String],
+    config: &Config,
+    options: UpdateDependenciesOptions,
+) -> Result<Vec<String>> {
+    if names.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut dependencies_spinner = spinner(
+        "Removing dependencies.",
+        SpinnerOptions {
+            silent: options.silent,
+        },
+    );
+
+    match remove_from_manifest(names, config).await {
+        Ok(removed) => {
+            dependencies_spinner.succeed(None);
+
+            Ok(removed)
+        }
+        Err(error) => {
+            dependencies_spinner.fail();
+
+            LOGGER.r#break();
+            LOGGER.error("Failed to remove dependencies.");
+            LOGGER.error(&error.to_string());
+            LOGGER.r#break();
+
+            bail!("");
+        }
+    }
+}
+
+async fn remove_from_manifest(names: &[String], config: &Config) -> Result<Vec<String>> {
+    let manifest_path = find_package_manifest(config).await?;
+    let content = read_file(&manifest_path)
+        .await
+        .with_context(|| format!("Failed to read {}.", manifest_path.display()))?;
+    let mut manifest = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse {}.", manifest_path.display()))?;
+
+    let Some(table) = manifest
+        .get_mut("dependencies")
+        .and_then(|dependencies| dependencies.as_table_like_mut())
+    else {
+        return Ok(vec![]);
+    };
+
+    // Match renamed dependencies by their package name, like when adding them.
+    let keys = table
+        .iter()
+        .filter(|(key, item)| {
+            let name = item
+                .get("package")
+                .and_then(|package| package.as_str())
+                .unwrap_or(key);
+            names.iter().any(|candidate| candidate == name)
+        })
+        .map(|(key, _)| key.to_string())
+        .collect::<Vec<_>>();
+
+    for key in &keys {
+        table.remove(key);
+    }
+    record_dependencies(&[], &keys);
+
+    let updated_content = manifest.to_string();
+    if updated_content != content {
+        write_file(&manifest_path, updated_content).await?;
+    }
+
+    Ok(keys)
+}
+
+/// Find the manifest of the package which components are added to.
+///
+/// In a virtual workspace, this is the member containing the components directory, or else the only member.
+async fn find_package_manifest(config: &Config) -> Result<PathBuf> {
+    let cwd = &config.resolved_paths.cwd;
+    let manifest_path = cwd.join("Cargo.toml");
+    let content = read_file(&manifest_path)
+        .await
+        .with_context(|| format!("Failed to read {}.", manifest_path.display()))?;
+    let manifest = toml::from_str::<toml::Table>(&content)
+        .with_context(|| format!("Failed to parse {}.", manifest_path.display()))?;
+
+    if manifest.contains_key("package") {
+        return Ok(manifest_path);
+    }
+
+    let members = get_workspace_members(cwd, &manifest).await?;
+    let member = members
+        .iter()
+        .filter(|member| config.resolved_paths.components.starts_with(member))
+        .max_by_key(|member| member.components().count())
+        .or(match members.as_slice() {
+            [member] => Some(member),
+            _ => None,
+        });
+
+    match member {
+        Some(member) => Ok(member.join("Cargo.toml")),
+        None => bail!(
+            "{} is not a package manifest and none of its workspace members contains {}. Run the command in the package to add components to.",
+            HIGHLIGHTER.info(&manifest_path.to_string_lossy()),
+            HIGHLIGHTER.info(&config.resolved_paths.components.to_string_lossy())
+        ),
+    }
+}
+
+/// Merge dependencies on the same crate, combining their features.
+fn merge_dependencies(
+    dependencies: &[RegistryItemDependency],
+) -> BTreeMap<String, CrateDependency> {
+    let mut merged: BTreeMap<String, CrateDependency> = BTreeMap::new();
+
+    for dependency in dependencies.iter().map(RegistryItemDependency::to_crate) {
+        match merged.get_mut(&dependency.name) {
+            Some(existing) => {
+                let mut features = existing.features.take().unwrap_or_default();
+                for feature in dependency.features.into_iter().flatten() {
+                    if !features.contains(&feature) {
+                        features.push(feature);
+                    }
+                }
+                existing.features = Some(features);
+
+                existing.version = existing.version.take().or(dependency.version);
+                existing.default_features =
+                    existing.default_features.or(dependency.default_features);
+                existing.git = existing.git.take().or(dependency.git);
+                existing.branch = existing.branch.take().or(dependency.branch);
+                existing.tag = existing.tag.take().or(dependency.tag);
+                existing.rev = existing.rev.take().or(dependency.rev);
+            }
+            None => {
+                merged.insert(dependency.name.clone(), dependency);
+            }
+        }
+    }
+
+    merged
+}
+
+/// Add features to an existing dependency, converting a version string into an inline table if needed.
+fn add_features(item: &mut Item, features: &BTreeSet<String>) {
+    if features.is_empty() {
+        return;
+    }
+
+    if let Some(Value::String(version)) = item.as_value() {
+        let mut dependency = InlineTable::new();
+        dependency.insert("version", version.value().as_str().into());
+
+        let mut dependency = Value::InlineTable(dependency);
+        *dependency.decor_mut() = version.decor().clone();
+        *item = Item::Value(dependency);
+    }
+
+    let Some(dependency) = item.as_table_like_mut() else {
+        return;
+    };
+
+    let existing = dependency
+        .get("features")
+        .and_then(|features| features.as_array())
+        .map(|features| {
+            features
+                .iter()
+                .filter_map(|feature| feature.as_str().map(String::from))
+                .collect::<BTreeSet<_>>()
+        })
+        .unwrap_or_default();
+
+    let missing = features.difference(&existing).collect::<Vec<_>>();
+    if missing.is_empty() {
+        return;
+    }
+
+    match dependency
+        .get_mut("features")
+        .and_then(|features| features.as_array_mut())
+    {
+        Some(array) => {
+            for feature in missing {
+                array.push(feature.as_str());
+            }
+        }
+        None => {
+            dependency.insert("features", value(features_array(features)));
+        }
+    }
+
+    // Space the added key like the others, e.g. not `version = "1.0.0" , features = [...]`.
+    if let Some(dependency) = item.as_inline_table_mut() {
+        dependency.fmt();
+    }
+}
+
+fn features_array(features: &BTreeSet<String>) -> Array {
+    features.iter().map(String::as_str).collect()
+}
+
+/// Find the names of `[workspace.dependencies]` in the workspace containing `cwd`, if any.
+async fn get_workspace_dependencies(cwd: &Path) -> Result<BTreeSet<String>> {
+    let Some(workspace_path) = find_workspace_manifest(cwd).await? else {
+        return Ok(BTreeSet::new());
+    };
+
+    let content = read_file(&workspace_path).await?;
+    let manifest = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse {}.", workspace_path.display()))?;
+
+    Ok(manifest
+        .get("workspace")
+        .and_then(|workspace| workspace.get("dependencies"))
+        .and_then(|dependencies| dependencies.as_table_like())
+        .map(|dependencies| {
+            dependencies
+                .iter()
+                .map(|(key, _)| key.to_string())
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Find the nearest `Cargo.toml` with a `[workspace]` table in `cwd` or its ancestors.
+async fn find_workspace_manifest(cwd: &Path) -> Result<Option<PathBuf>> {
+    let cwd = canonicalize(cwd).await?;
+
+    for directory in cwd.ancestors() {
+        let path = directory.join("Cargo.toml");
+        if !path_exists(&path).await? {
+            continue;
+        }
+
+        let content = read_file(&path).await?;
+        if content
+            .parse::<DocumentMut>()
+            .is_ok_and(|manifest| manifest.contains_table("workspace"))
+        {
+            return Ok(Some(path));
+        }
+    }
+
+    Ok(None)
+}
+
+#[derive(Deserialize)]
+struct IndexEntry {
+    vers: String,
+    yanked: bool,
+}
+
+/// Get the latest stable version of a crate from the crates.io index.
+async fn get_latest_version(name: &str) -> Result<String> {
+    let name = name.to_lowercase();
+    let prefix = match name.len() {
+        1 => "1".into(),
+        2 => "2".into(),
+        3 => format!("3/{}", &name[..1]),
+        _ => format!("{}/{}", &name[..2], &name[2..4]),
+    };
+
+    let url = format!("{CRATES_IO_INDEX_URL}/{prefix}/{name}");
+    let response = reqwest::get(&url)
+        .await
+        .and_then(|response| response.error_for_status())
+        .with_context(|| {
+            format!(
+                "Failed to find crate {} on crates.io.",
+                HIGHLIGHTER.info(&name)
+            )
+        })?;
+    let content = response.text().await?;
+
+    content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<IndexEntry>(line).ok())
+        .filter(|entry| !entry.yanked)
+        .filter_map(|entry| Version::parse(&entry.vers).ok())
+        .filter(|version| version.pre.is_empty())
+        .max()
+        .map(|version| version.to_string())
+        .with_context(|| {
+            format!(
+                "No stable version of crate {} found on crates.io.",
+                HIGHLIGHTER.info(&name)
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use shadcn_registry::schema::CrateDependency;
+
+    use super::*;
+    use crate::utils::{
+        get_config::{PathsConfig, resolve_config_paths},
+        test_utils::{TempDir, config, raw_config},
+    };
+
+    const PACKAGE: &str = "[package]\nname = \"app\"\nversion = \"0.1.0\"\nedition = \"2024\"\n";
+
+    #[tokio::test]
+    async fn adds_dependencies() {
+        let dir = TempDir::new();
+        dir.write(
+            "Cargo.toml",
+            &format!(
+                "{PACKAGE}\n[dependencies]\n# UI\nleptos = \"0.8.0\" # Framework\nicons = {{ package = \"lucide-leptos\", version = \"2.0.0\" }}\n"
+            ),
+        );
+        let config = config(dir.path()).await;
+
+        update_manifest(
+            &[
+                "tailwind_fuse@0.3.2".into(),
+                "leptos@0.8.0/nightly".into(),
+                "lucide-leptos/notifications".into(),
+                CrateDependency {
+                    name: "leptos-node-ref".into(),
+                    version: Some("0.2.0".into()),
+                    default_features: Some(false),
+                    ..Default::default()
+                }
+                .into(),
+            ],
+            &config,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            dir.read("Cargo.toml"),
+            format!(
+                "{PACKAGE}\n[dependencies]\n# UI\nleptos = {{ version = \"0.8.0\", features = [\"nightly\"] }} # Framework\nicons = {{ package = \"lucide-leptos\", version = \"2.0.0\", features = [\"notifications\"] }}\nleptos-node-ref = {{ version = \"0.2.0\", default-features = false }}\ntailwind_fuse = \"0.3.2\"\n"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn adds_workspace_dependencies() {
+        let dir = TempDir::new();
+        dir.write(
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"app\"]\n\n[workspace.dependencies]\ntailwind_fuse = { version = \"0.3.2\", default-features = false }\n",
+        );
+        dir.write("app/Cargo.toml", PACKAGE);
+        let config = config(&dir.path().join("app")).await;
+
+        update_manifest(
+            &[CrateDependency {
+                name: "tailwind_fuse".into(),
+                version: Some("0.3.2".into()),
+                features: Some(vec!["variant".into()]),
+                default_features: Some(false),
+                ..Default::default()
+            }
+            .into()],
+            &config,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            dir.read("app/Cargo.toml"),
+            format!(
+                "{PACKAGE}\n[dependencies]\ntailwind_fuse = {{ workspace = true, features = [\"variant\"] }}\n"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn finds_member_of_virtual_workspace() {
+        let dir = TempDir::new();
+        dir.write(
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/legacy\"]\n",
+        );
+        dir.write("crates/app/Cargo.toml", PACKAGE);
+        dir.write("crates/legacy/Cargo.toml", PACKAGE);
+        let config = config(dir.path()).await;
+
+        assert_eq!(
+            find_package_manifest(&config).await.unwrap(),
+            dir.path().join("crates/app/Cargo.toml")
+        );
+
+        dir.write("crates/ui/Cargo.toml", PACKAGE);
+        assert!(find_package_manifest(&config).await.is_err());
+
+        let config = resolve_config_paths(
+            dir.path(),
+            raw_config(PathsConfig {
+                components: Some("crates/ui/src/components".into()),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            find_package_manifest(&config).await.unwrap(),
+            dir.path().join("crates/ui/Cargo.toml")
+        );
+    }
+
+    #[tokio::test]
+    async fn removes_dependencies() {
+        let dir = TempDir::new();
+        dir.write(
+            "Cargo.toml",
+            &format!(
+                "{PACKAGE}\n[dependencies]\nicons = {{ package = \"lucide-leptos\", version = \"2.0.0\" }}\nleptos = \"0.8.0\"\ntailwind_fuse = \"0.3.2\"\n"
+            ),
+        );
+        let config = config(dir.path()).await;
+
+        let removed = remove_from_manifest(
+            &[
+                "lucide-leptos".into(),
+                "tailwind_fuse".into(),
+                "itertools".into(),
+            ],
+            &config,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(removed, ["icons", "tailwind_fuse"]);
+        assert_eq!(
+            dir.read("Cargo.toml"),
+            format!("{PACKAGE}\n[dependencies]\nleptos = \"0.8.0\"\n")
+        );
+    }
+}
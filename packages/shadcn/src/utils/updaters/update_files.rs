@@ -0,0 +1,128 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use shadcn_registry::schema::RegistryItemFile;
+
+use crate::utils::{
+    base_files::write_base_file,
+    get_config::Config,
+    highlighter::HIGHLIGHTER,
+    logger::LOGGER,
+    modules::{add_module_declarations, get_module_layout},
+    plan::{path_exists, write_file},
+    registry::get_registry_item_file_path,
+    spinner::{SpinnerOptions, spinner},
+};
+
+pub struct UpdateFilesOptions<'a> {
+    pub overwrite: bool,
+    pub silent: bool,
+    pub path: Option<&'a Path>,
+}
+
+pub async fn update_files(
+    files: &[RegistryItemFile],
+    config: &Config,
+    options: UpdateFilesOptions<'_>,
+) -> Result<()> {
+    if files.is_empty() {
+        return Ok(());
+    }
+
+    let mut files_created_spinner = spinner(
+        "Updating files.",
+        SpinnerOptions {
+            silent: options.silent,
+        },
+    );
+
+    let mut files_created: Vec<PathBuf> = vec![];
+    let mut files_updated: Vec<PathBuf> = vec![];
+    let mut files_skipped: Vec<PathBuf> = vec![];
+    let mut files_written: Vec<PathBuf> = vec![];
+
+    for file in files {
+        let Some(content) = &file.content else {
+            continue;
+        };
+
+        let file_path = get_registry_item_file_path(file, config, options.path);
+        let relative_path = file_path
+            .strip_prefix(&config.resolved_paths.cwd)
+            .unwrap_or(&file_path)
+            .to_path_buf();
+
+        let existing_file = path_exists(&file_path).await?;
+        if existing_file && !options.overwrite {
+            files_skipped.push(relative_path);
+            continue;
+        }
+
+        write_file(&file_path, content.as_str()).await?;
+        write_base_file(&file_path, content, config).await?;
+        files_written.push(file_path);
+
+        if existing_file {
+            files_updated.push(relative_path);
+        } else {
+            files_created.push(relative_path);
+        }
+    }
+
+    // Make the written files reachable from the crate root.
+    let layout = get_module_layout(config).await?;
+    let modules = add_module_declarations(&files_written, config, layout).await?;
+    for (paths, modules) in [
+        (&mut files_created, modules.created),
+        (&mut files_updated, modules.updated),
+    ] {
+        for module in modules {
+            if !paths.contains(&module) {
+                paths.push(module);
+            }
+        }
+    }
+
+    let has_updated_files = !files_created.is_empty() || !files_updated.is_empty();
+    if has_updated_files {
+        files_created_spinner.succeed(None);
+    } else {
+        files_created_spinner.fail();
+        if !options.silent {
+            LOGGER.info("No files updated.");
+        }
+    }
+
+    if options.silent {
+        return Ok(());
+    }
+
+    for (label, paths) in [
+        ("Created", &files_created),
+        ("Updated", &files_updated),
+        ("Skipped", &files_skipped),
+    ] {
+        if paths.is_empty() {
+            continue;
+        }
+
+        LOGGER.log(&format!(
+            "{} {} {}:",
+            label,
+            paths.len(),
+            if paths.len() == 1 { "file" } else { "files" }
+        ));
+        for path in paths {
+            LOGGER.log(&format!("  - {}", path.display()));
+        }
+    }
+
+    if !files_skipped.is_empty() {
+        LOGGER.info(&format!(
+            "Use {} to overwrite existing files.",
+            HIGHLIGHTER.info("--overwrite")
+        ));
+    }
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, env, fs, path::Path};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use convert_case::{Case, Casing};
 use handlebars::Handlebars;
 use regex::Regex;
@@ -9,12 +9,13 @@ use serde_json::json;
 use shadcn_registry::{
     REGISTRY,
     registry_base_colors::BASE_COLORS,
-    registry_colors::{COLOR_MAPPING, COLORS, Color},
+    registry_colors::{COLOR_MAPPING, COLORS, Color, Oklch, Rgb},
     registry_frameworks::FRAMEWORKS,
+    registry_resolver::resolve_registry_items,
     registry_styles::STYLES,
     schema::{
-        Mode, RegistryEntry, RegistryItemFile, RegistryItemTailwind, RegistryItemTailwindConfig,
-        RegistryItemType, Style,
+        CrateDependency, FrameworkName, Mode, RegistryBaseColor, RegistryEntry, RegistryItemFile,
+        RegistryItemTailwind, RegistryItemTailwindConfig, RegistryItemType, Style,
     },
 };
 
@@ -64,16 +65,24 @@ fn build_registry(output_path: &Path) -> Result<()> {
 fn build_styles(input_path: &Path, output_path: &Path) -> Result<()> {
     for (framework, registry) in REGISTRY.iter() {
         let target_path = output_path.join(format!("r/frameworks/{framework}"));
+        let names = registry
+            .iter()
+            .map(|item| item.name.clone())
+            .collect::<Vec<_>>();
 
         for style in STYLES {
             let target_path = target_path.join(format!("styles/{}", style.name));
 
+            // Ensure all registry dependencies exist and do not form cycles.
+            let plan = resolve_registry_items(&names, *framework, style.name)
+                .with_context(|| format!("Invalid {framework} registry."))?;
+
             // Create directory if it doesn't exist.
             if !target_path.exists() {
                 fs::create_dir_all(&target_path)?;
             }
 
-            for item in registry {
+            for item in &plan.items {
                 if !REGISTRY_INDEX_WHITELIST.contains(&item.r#type) {
                     continue;
                 }
@@ -133,17 +142,44 @@ fn build_styles_index(output_path: &Path) -> Result<()> {
                 framework.name, style.name
             ));
 
-            // TODO: Rustify dependencies
-
-            let mut dependencies: Vec<String> = vec![
-                "tailwindcss-animate".into(),
-                "class-variance-authority".into(),
-                "lucide-react".into(),
+            let mut dependencies = vec![
+                CrateDependency {
+                    name: "tailwind_fuse".into(),
+                    version: Some("0.3.0".into()),
+                    features: Some(vec!["variant".into()]),
+                    ..Default::default()
+                }
+                .into(),
+                CrateDependency {
+                    name: format!("lucide-{}", framework.name),
+                    version: Some("2.0.0".into()),
+                    ..Default::default()
+                }
+                .into(),
             ];
 
-            // TODO: Remove this when we migrate to lucide-react.
+            // TODO: Remove this when we migrate to Lucide.
             if style.name == Style::NewYork {
-                dependencies.push("@radix-ui/react-icons".into());
+                match framework.name {
+                    FrameworkName::Leptos => dependencies.push(
+                        CrateDependency {
+                            name: "radix-leptos-icons".into(),
+                            git: Some("https://github.com/RustForWeb/radix.git".into()),
+                            ..Default::default()
+                        }
+                        .into(),
+                    ),
+                    FrameworkName::Yew => dependencies.push(
+                        CrateDependency {
+                            name: "radix-yew-icons".into(),
+                            version: Some("0.0.2".into()),
+                            ..Default::default()
+                        }
+                        .into(),
+                    ),
+                    // TODO: Radix Icons are not available for Dioxus yet.
+                    FrameworkName::Dioxus => {}
+                }
             }
 
             let payload = RegistryEntry {
@@ -192,6 +228,7 @@ fn build_themes(output_path: &Path) -> Result<()> {
         pub hex: String,
         pub rgb: String,
         pub hsl: String,
+        pub oklch: String,
         pub rgb_channel: String,
         pub hsl_channel: String,
     }
@@ -203,6 +240,7 @@ fn build_themes(output_path: &Path) -> Result<()> {
         pub hex: String,
         pub rgb: String,
         pub hsl: String,
+        pub oklch: String,
         pub rgb_channel: String,
         pub hsl_channel: String,
     }
@@ -227,6 +265,7 @@ fn build_themes(output_path: &Path) -> Result<()> {
                     hex: value.hex.clone(),
                     rgb: value.rgb.clone(),
                     hsl: value.hsl.clone(),
+                    oklch: value.oklch().to_string(),
                     rgb_channel: rgb_regex.replace(&value.rgb, "$1 $2 $3").to_string(),
                     hsl_channel: hsl_regex.replace(&value.hsl, "$1 $2 $3").to_string(),
                 }),
@@ -238,6 +277,7 @@ fn build_themes(output_path: &Path) -> Result<()> {
                             hex: value.hex.clone(),
                             rgb: value.rgb.clone(),
                             hsl: value.hsl.clone(),
+                            oklch: value.oklch().to_string(),
                             rgb_channel: rgb_regex.replace(&value.rgb, "$1 $2 $3").to_string(),
                             hsl_channel: hsl_regex.replace(&value.hsl, "$1 $2 $3").to_string(),
                         })
@@ -255,29 +295,30 @@ fn build_themes(output_path: &Path) -> Result<()> {
     const BASE_STYLES: &str = include_str!("templates/base_styles.css");
     const BASE_STYLES_WITH_VARIABLES: &str =
         include_str!("templates/base_styles_with_variables.css");
-
-    #[derive(Clone, Debug, Default, Deserialize, Serialize)]
-    #[serde(rename_all = "camelCase")]
-    struct BaseColor {
-        inline_colors: HashMap<Mode, HashMap<String, String>>,
-        css_vars: HashMap<Mode, HashMap<String, String>>,
-        inline_colors_template: String,
-        css_vars_template: String,
-    }
+    const BASE_STYLES_V4: &str = include_str!("templates/base_styles_v4.css");
+    const BASE_STYLES_WITH_VARIABLES_V4: &str =
+        include_str!("templates/base_styles_with_variables_v4.css");
 
     let base_color_regex = Regex::new(r"\{\{base\}\}-").expect("Regex should be valid.");
 
-    for base_color in ["slate", "gray", "zinc", "neutral", "stone"] {
-        let mut base = BaseColor::default();
+    for base_color in BASE_COLORS
+        .iter()
+        .map(|base_color| base_color.name.as_str())
+    {
+        let mut base = RegistryBaseColor::default();
+        // Tailwind CSS v4 uses complete colors in OKLCH instead of HSL channels.
+        let mut css_vars_v4 = HashMap::new();
 
         for (mode, values) in COLOR_MAPPING.iter() {
             let mut inline_colors = HashMap::new();
             let mut css_vars = HashMap::new();
+            let mut mode_css_vars_v4 = HashMap::new();
 
             for (key, value) in values {
                 // Chart colors do not have a 1-to-1 mapping with Tailwind colors.
                 if key.starts_with("chart-") {
                     css_vars.insert(key.clone(), value.clone());
+                    mode_css_vars_v4.insert(key.clone(), hsl_channel_to_oklch(value));
                     continue;
                 }
 
@@ -292,22 +333,27 @@ fn build_themes(output_path: &Path) -> Result<()> {
                 let color = color_data.get(resolved_base).and_then(|color| match scale {
                     Some(scale) => match color {
                         JsonColor::Values(values) => values.iter().find_map(|value| {
-                            (value.scale == scale).then_some(value.hsl_channel.clone())
+                            (value.scale == scale)
+                                .then_some((value.hsl_channel.clone(), value.oklch.clone()))
                         }),
                         _ => unreachable!("Color must be a scale."),
                     },
                     None => match color {
-                        JsonColor::Value(value) => Some(value.hsl_channel.clone()),
+                        JsonColor::Value(value) => {
+                            Some((value.hsl_channel.clone(), value.oklch.clone()))
+                        }
                         _ => unreachable!("Color must not be a string or a scale."),
                     },
                 });
-                if let Some(color) = color {
-                    css_vars.insert(key.clone(), color);
+                if let Some((hsl_channel, oklch)) = color {
+                    css_vars.insert(key.clone(), hsl_channel);
+                    mode_css_vars_v4.insert(key.clone(), oklch);
                 }
             }
 
             base.inline_colors.insert(*mode, inline_colors);
             base.css_vars.insert(*mode, css_vars);
+            css_vars_v4.insert(*mode, mode_css_vars_v4);
         }
 
         // Build CSS vars.
@@ -318,6 +364,13 @@ fn build_themes(output_path: &Path) -> Result<()> {
                 "colors": &base.css_vars
             }),
         )?;
+        base.inline_colors_template_v4 = handlebars.render_template(BASE_STYLES_V4, &())?;
+        base.css_vars_template_v4 = handlebars.render_template(
+            BASE_STYLES_WITH_VARIABLES_V4,
+            &json!({
+                "colors": &css_vars_v4
+            }),
+        )?;
 
         let base_json = serde_json::to_string_pretty(&base)?;
         fs::write(
@@ -327,8 +380,11 @@ fn build_themes(output_path: &Path) -> Result<()> {
 
         const THEME_STYLES_WITH_VARIABLES: &str =
             include_str!("templates/theme_styles_with_variables.css");
+        const THEME_STYLES_WITH_VARIABLES_V4: &str =
+            include_str!("templates/theme_styles_with_variables_v4.css");
 
         let mut theme_css = vec![];
+        let mut theme_css_v4 = vec![];
         for theme in BASE_COLORS.iter() {
             theme_css.push(handlebars.render_template(
                 THEME_STYLES_WITH_VARIABLES,
@@ -337,9 +393,30 @@ fn build_themes(output_path: &Path) -> Result<()> {
                     "theme": theme.name
                 }),
             )?);
+
+            let css_vars_v4 = theme
+                .css_vars
+                .iter()
+                .map(|(mode, vars)| {
+                    (
+                        *mode,
+                        vars.iter()
+                            .map(|(key, value)| (key.clone(), hsl_channel_to_oklch(value)))
+                            .collect::<HashMap<_, _>>(),
+                    )
+                })
+                .collect::<HashMap<_, _>>();
+            theme_css_v4.push(handlebars.render_template(
+                THEME_STYLES_WITH_VARIABLES_V4,
+                &json!({
+                    "colors": css_vars_v4,
+                    "theme": theme.name
+                }),
+            )?);
         }
 
         fs::write(output_path.join("r/themes.css"), theme_css.join("\n"))?;
+        fs::write(output_path.join("r/themes-v4.css"), theme_css_v4.join("\n"))?;
 
         #[derive(Clone, Debug, Default, Deserialize, Serialize)]
         #[serde(rename_all = "camelCase")]
@@ -408,6 +485,13 @@ fn build_themes(output_path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Convert HSL channels, e.g. `240 5.9% 10%`, to an OKLCH color. Other values, such as `0.5rem`, are kept.
+fn hsl_channel_to_oklch(value: &str) -> String {
+    Rgb::from_hsl_channel(value)
+        .map(|rgb| Oklch::from(rgb).to_string())
+        .unwrap_or_else(|| value.into())
+}
+
 fn main() -> Result<()> {
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
 